@@ -1,5 +1,56 @@
 //! Core types and traits for StreamGuard
 
+use alloc::string::String;
+
+/// Structured detail about the match that produced a rule's decision.
+///
+/// Unlike [`Decision`] (which only says *that* something matched), this
+/// carries *what* matched: the rule's label, where the match sits in the
+/// cumulative stream, and -- for rules that know how to decompose their
+/// match -- its parsed sub-components (e.g. an email's local part and
+/// domain). This lets a caller log or redact precisely (`john@example.com`
+/// -> `j***@example.com`) instead of only knowing a block happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchInfo {
+    /// Label identifying which rule/pattern produced the match.
+    pub rule: String,
+    /// Byte offsets `(start, end)` of the match within the cumulative
+    /// stream this rule has been fed.
+    pub span: (usize, usize),
+    /// Parsed sub-components, if the rule knows how to split its match.
+    pub components: MatchComponents,
+}
+
+/// Parsed sub-components of a match, broken out by the pattern kind that
+/// produced it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchComponents {
+    /// No structured breakdown available for this match.
+    None,
+    /// An email address, split at `@`.
+    Email {
+        /// The part before `@`.
+        local: String,
+        /// The part after `@`.
+        domain: String,
+    },
+    /// A URL, split into scheme, host, and path.
+    Url {
+        /// e.g. `"https"`.
+        scheme: String,
+        /// e.g. `"example.com"`.
+        host: String,
+        /// e.g. `"/path"`, empty if the URL had none.
+        path: String,
+    },
+    /// A credit card number, masked down to its last 4 digits.
+    CreditCard {
+        /// The matched number with all but the last 4 digits replaced by
+        /// `*`, e.g. `"**** **** **** 1234"`.
+        masked: String,
+    },
+}
+
 /// Decision returned by a rule or the engine
 ///
 /// Decisions are final and immediate - they determine what happens
@@ -16,10 +67,32 @@ pub enum Decision {
     },
 
     /// Rewrite the input with replacement text
+    ///
+    /// Also the decision a redacting rule returns (see
+    /// [`crate::rules::PatternRule::redact`]): there is no separate
+    /// "redacted" variant because masking a match and swapping it for other
+    /// replacement text are the same operation from the engine's point of
+    /// view -- both hand back sanitized text to forward on instead of the
+    /// original chunk.
     Rewrite {
         /// Text to emit instead of the original input
         replacement: String,
     },
+
+    /// Tag the input without stopping the stream or replacing its text
+    ///
+    /// The original chunk keeps flowing through as if `Allow` had been
+    /// returned; the caller is additionally handed a marker/reason/score so
+    /// it can surface a warning on borderline content, the way a spam milter
+    /// prepends `X-Spam-` headers rather than dropping the mail outright.
+    Annotate {
+        /// Short machine-readable tag, e.g. a rule or category name
+        marker: String,
+        /// Human-readable reason for the annotation
+        reason: String,
+        /// Score contribution that triggered the annotation
+        score: u32,
+    },
 }
 
 /// Extended decision with scoring information
@@ -61,6 +134,13 @@ impl Decision {
             _ => None,
         }
     }
+
+    /// Returns true if this decision annotates the input without blocking
+    /// or rewriting it
+    #[inline]
+    pub fn is_annotate(&self) -> bool {
+        matches!(self, Decision::Annotate { .. })
+    }
 }
 
 /// A streaming rule that inspects text incrementally
@@ -69,7 +149,7 @@ impl Decision {
 /// - **Incremental**: Process input chunk-by-chunk
 /// - **Stateful**: Maintain internal state across chunks
 /// - **Cheap**: Evaluate efficiently without allocations
-pub trait Rule: Send + Sync {
+pub trait Rule: Send + Sync + 'static {
     /// Process a chunk of text and return a decision
     ///
     /// The chunk may be arbitrarily small (even a single character)
@@ -100,6 +180,85 @@ pub trait Rule: Send + Sync {
     fn last_score(&self) -> u32 {
         0
     }
+
+    /// Optional: the `(start, end)` char offsets of the most recent match,
+    /// relative to the cumulative stream this rule has been fed (i.e. not
+    /// reset by chunk boundaries). `None` if the rule doesn't track spans or
+    /// the last decision had no match.
+    fn last_match_span(&self) -> Option<(usize, usize)> {
+        None
+    }
+
+    /// Optional: structured detail about the most recent match -- the rule
+    /// label, its byte span in the cumulative stream, and parsed
+    /// sub-components for rules that know how to decompose their match
+    /// (e.g. [`crate::rules::PatternRule`] for email/URL/credit-card
+    /// matches). `None` if the rule doesn't support structured extraction or
+    /// the last decision had no match.
+    ///
+    /// Note the span here is in **bytes**, unlike [`Self::last_match_span`]
+    /// (which is in chars) -- each tracks offsets the way its own
+    /// implementing rule already indexes text internally.
+    fn last_match_info(&self) -> Option<MatchInfo> {
+        None
+    }
+
+    /// Optional: emit any text a rule is withholding pending a match that
+    /// straddles the end of the stream, ruling it out now that nothing more
+    /// is coming to complete it (see
+    /// [`crate::rules::PatternRule::redact`]/[`crate::rules::PatternRule::redact_masked`]).
+    /// Most rules withhold nothing and use the default.
+    fn flush(&mut self) -> Decision {
+        Decision::Allow
+    }
+
+    /// Optional: expose this rule as `&dyn Any` so the engine can downcast
+    /// `Box<dyn Rule>` back to a concrete rule type it knows how to batch
+    /// into a shared automaton (see the single-token fast path in
+    /// `GuardEngine::evaluate_chunk`, which looks for
+    /// [`crate::rules::ForbiddenSequenceRule`] this way). Most rules never
+    /// get downcast and use the default.
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+
+    /// Mutable counterpart to [`Self::as_any`].
+    fn as_any_mut(&mut self) -> &mut dyn core::any::Any {
+        self
+    }
+}
+
+/// An asynchronous streaming rule that may perform I/O while deciding.
+///
+/// `AsyncRule` mirrors [`Rule`] but its `feed` is `async`, so a rule can call
+/// out to an external moderation service, a hosted LLM classifier, or an
+/// embedding lookup without blocking the streaming path. Async rules are
+/// driven by [`GuardEngine::feed_async`](crate::GuardEngine::feed_async) on a
+/// bounded background worker pool.
+///
+/// The trait is boxed into the engine as `Box<dyn AsyncRule>`, so it is made
+/// object-safe via `async_trait`.
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+pub trait AsyncRule: Send + Sync {
+    /// Asynchronously process a chunk of text and return a decision.
+    ///
+    /// Like [`Rule::feed`], the chunk may be arbitrarily small or large and
+    /// the rule must handle partial matches across chunk boundaries.
+    async fn feed(&mut self, chunk: &str) -> Decision;
+
+    /// Reset the rule's internal state.
+    fn reset(&mut self);
+
+    /// Optional: Get a human-readable name for this rule.
+    fn name(&self) -> &str {
+        "unnamed_async_rule"
+    }
+
+    /// Optional: Get the score for the last decision (0 if no scoring or no match).
+    fn last_score(&self) -> u32 {
+        0
+    }
 }
 
 #[cfg(test)]
@@ -125,5 +284,15 @@ mod tests {
         assert!(!rewrite.is_allow());
         assert!(!rewrite.is_block());
         assert!(rewrite.is_rewrite());
+
+        let annotate = Decision::Annotate {
+            marker: "tag".to_string(),
+            reason: "test".to_string(),
+            score: 1,
+        };
+        assert!(!annotate.is_allow());
+        assert!(!annotate.is_block());
+        assert!(!annotate.is_rewrite());
+        assert!(annotate.is_annotate());
     }
 }
@@ -1,6 +1,99 @@
 //! The main GuardEngine that orchestrates rules and decisions
 
-use crate::core::{Decision, Rule};
+use crate::core::{Decision, MatchInfo, Rule, ScoredDecision};
+
+#[cfg(feature = "async")]
+use alloc::boxed::Box;
+#[cfg(feature = "async")]
+use crate::core::AsyncRule;
+#[cfg(feature = "async")]
+use crate::worker::{WorkerConfig, WorkerPool};
+
+/// An injectable source of monotonic time for wall-clock score decay.
+///
+/// Returning seconds as an `f64` keeps the abstraction `no_std`-friendly and
+/// lets tests supply a deterministic fake clock instead of wall time.
+pub trait Clock: Send + Sync {
+    /// Current monotonic time, in seconds.
+    fn now_secs(&self) -> f64;
+}
+
+/// Default [`Clock`] backed by `std::time::Instant`.
+///
+/// Only available with the `std` feature; reports seconds elapsed since the
+/// clock was created.
+#[cfg(feature = "std")]
+pub struct SystemClock {
+    start: std::time::Instant,
+}
+
+#[cfg(feature = "std")]
+impl SystemClock {
+    /// Create a clock anchored at the current instant.
+    pub fn new() -> Self {
+        Self {
+            start: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now_secs(&self) -> f64 {
+        self.start.elapsed().as_secs_f64()
+    }
+}
+
+/// Observer invoked when `feed` produces a [`Decision::Block`].
+///
+/// Receives the block reason and the engine's accumulated score. Bounded
+/// `Send + Sync` like the config rule factories so the engine stays shareable.
+type BlockObserver = Box<dyn FnMut(&str, u32) + Send + Sync>;
+
+/// Observer invoked when `feed` produces a [`Decision::Rewrite`].
+///
+/// Receives the replacement text and the engine's accumulated score.
+type RewriteObserver = Box<dyn FnMut(&str, u32) + Send + Sync>;
+
+/// Adapts a rule so its `Block` decisions surface as `Annotate` tags
+/// instead of stopping the stream. `Rewrite` and `Allow` pass through
+/// unchanged -- annotation only softens blocking, not rewriting.
+struct AnnotateRule {
+    inner: Box<dyn Rule>,
+    marker: String,
+}
+
+impl Rule for AnnotateRule {
+    fn feed(&mut self, chunk: &str) -> Decision {
+        match self.inner.feed(chunk) {
+            Decision::Block { reason } => Decision::Annotate {
+                marker: self.marker.clone(),
+                reason,
+                score: self.inner.last_score(),
+            },
+            other => other,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn last_score(&self) -> u32 {
+        self.inner.last_score()
+    }
+}
 
 /// Engine mode for handling rewrites
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -11,6 +104,148 @@ pub enum RewriteMode {
     Chain,
 }
 
+/// The kind of decision a rule contributed to a [`StreamReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchKind {
+    /// The rule blocked the stream.
+    Block,
+    /// The rule rewrote the text.
+    Rewrite,
+    /// The rule tagged the stream without blocking or rewriting it.
+    Annotate,
+}
+
+/// A single rule that triggered during a [`GuardEngine::feed_report`] call.
+#[derive(Debug, Clone)]
+pub struct RuleMatch {
+    /// Name of the rule that fired.
+    pub rule: String,
+    /// The block reason, or the replacement text for a rewrite.
+    pub detail: String,
+    /// Score this match contributed to the running total.
+    pub score: u32,
+    /// Whether the rule blocked, rewrote, or annotated.
+    pub kind: MatchKind,
+    /// Byte offset in the stream (as seen by `feed_report`) at which the
+    /// chunk carrying this match completed.
+    pub offset: usize,
+    /// The text this rule was evaluating when it matched.
+    ///
+    /// This is the chunk (or chained-rewrite text) the rule saw, not a
+    /// precisely extracted sub-span of it: `feed_report` does not consult
+    /// [`Rule::last_match_span`] or [`Rule::last_match_info`] to narrow it
+    /// to the exact matched substring.
+    pub matched_text: String,
+}
+
+/// A structured, multi-match view of a single [`GuardEngine::feed_report`] call.
+///
+/// Unlike [`GuardEngine::feed`], which stops at the first rule that blocks,
+/// `feed_report` evaluates every rule and records each one that triggered, so
+/// callers building audit logs or dashboards can see all contributing rules
+/// rather than only the winning decision. The aggregated [`Decision`] and the
+/// running score preserve `feed`'s precedence exactly.
+#[derive(Debug, Clone)]
+pub struct StreamReport {
+    /// Every rule that triggered on this chunk, in evaluation order.
+    pub matches: Vec<RuleMatch>,
+    /// The aggregated decision, preserving `feed`'s precedence.
+    pub decision: Decision,
+    /// The running accumulated score after this chunk.
+    pub score: u32,
+}
+
+impl StreamReport {
+    /// Serialize every match as a flat JSON array, the way a linter emits a
+    /// findings list for CI consumption.
+    ///
+    /// Hand-rolled rather than routed through `serde_json` so this is
+    /// available without the `config` feature; each string field is escaped
+    /// for embedding in a JSON string literal.
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("[");
+        for (i, m) in self.matches.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push_str(&format!(
+                r#"{{"rule":"{}","kind":"{}","detail":"{}","matched_text":"{}","score":{},"offset":{},"total_score":{}}}"#,
+                json_escape(&m.rule),
+                match m.kind {
+                    MatchKind::Block => "block",
+                    MatchKind::Rewrite => "rewrite",
+                    MatchKind::Annotate => "annotate",
+                },
+                json_escape(&m.detail),
+                json_escape(&m.matched_text),
+                m.score,
+                m.offset,
+                self.score,
+            ));
+        }
+        out.push(']');
+        out
+    }
+
+    /// Serialize every match as a minimal SARIF (`runs`/`results`) document.
+    ///
+    /// Only the fields a SARIF viewer needs to place a finding are populated
+    /// (`ruleId`, `message`, and a `byteOffset` region) -- this is not a full
+    /// SARIF schema implementation, just enough shape for CI tools that
+    /// already consume SARIF from linters to ingest StreamGuard findings too.
+    pub fn to_sarif(&self) -> String {
+        let mut results = String::new();
+        for (i, m) in self.matches.iter().enumerate() {
+            if i > 0 {
+                results.push(',');
+            }
+            results.push_str(&format!(
+                r#"{{"ruleId":"{}","message":{{"text":"{}"}},"locations":[{{"physicalLocation":{{"region":{{"byteOffset":{}}}}}}}],"properties":{{"score":{},"totalScore":{}}}}}"#,
+                json_escape(&m.rule),
+                json_escape(&m.detail),
+                m.offset,
+                m.score,
+                self.score,
+            ));
+        }
+        format!(
+            r#"{{"version":"2.1.0","runs":[{{"tool":{{"driver":{{"name":"streamguard"}}}},"results":[{}]}}]}}"#,
+            results
+        )
+    }
+}
+
+/// Escape a string for embedding in a JSON string literal.
+fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Lets every eligible single-token, block-mode
+/// [`ForbiddenSequenceRule`](crate::rules::ForbiddenSequenceRule) added the
+/// normal way (`add_rule`) share one Aho-Corasick pass over a chunk, instead
+/// of each independently re-scanning it -- see
+/// [`ForbiddenSequenceRule::as_single_literal_block`](crate::rules::ForbiddenSequenceRule::as_single_literal_block)
+/// for which rules qualify, and [`GuardEngine::ensure_seq_fast_path`] for how
+/// this is (re)built.
+struct SequenceFastPath {
+    automaton: crate::rules::ahocorasick::AhoCorasick,
+    /// Parallel to the automaton's compiled phrase list: which `rules`
+    /// index each phrase's rule lives at.
+    rule_indices: Vec<usize>,
+}
+
 /// The main streaming guardrail engine
 ///
 /// `GuardEngine` orchestrates multiple rules and processes text streams
@@ -28,6 +263,20 @@ pub enum RewriteMode {
 /// // Process stream
 /// let decision = engine.feed("chunk of text");
 /// ```
+/// Lets every eligible single-token, block-mode
+/// [`ForbiddenSequenceRule`](crate::rules::ForbiddenSequenceRule) added the
+/// normal way (`add_rule`) share one Aho-Corasick pass over a chunk, instead
+/// of each independently re-scanning it -- see
+/// [`ForbiddenSequenceRule::as_single_literal_block`](crate::rules::ForbiddenSequenceRule::as_single_literal_block)
+/// for which rules qualify, and [`GuardEngine::ensure_seq_fast_path`] for how
+/// this is (re)built.
+struct SequenceFastPath {
+    automaton: crate::rules::ahocorasick::AhoCorasick,
+    /// Parallel to the automaton's compiled phrase list: which `rules`
+    /// index each phrase's rule lives at.
+    rule_indices: Vec<usize>,
+}
+
 pub struct GuardEngine {
     rules: Vec<Box<dyn Rule>>,
     stopped: bool,
@@ -36,6 +285,67 @@ pub struct GuardEngine {
     score_decay: f32,
     rewrite_mode: RewriteMode,
     score_details: Vec<(String, u32)>,
+    /// `(start, end)` char span reported by the rule that produced the most
+    /// recent blocking decision, if that rule tracks match spans.
+    last_match_span: Option<(usize, usize)>,
+    /// Structured detail about the match that produced the most recent
+    /// blocking decision, if the triggering rule supports it. See
+    /// [`Rule::last_match_info`].
+    last_match_info: Option<MatchInfo>,
+    /// Wall-clock decay rate in score points per second, if time decay is on.
+    time_decay: Option<f32>,
+    /// Clock backing wall-clock decay; injectable for deterministic tests.
+    clock: Option<Box<dyn Clock>>,
+    /// Timestamp (seconds) of the previous `feed`, for elapsed-time decay.
+    last_feed_secs: Option<f64>,
+    /// Unicode-evasion normalization pass applied to each chunk before the
+    /// inbound filter and every rule. See [`crate::normalize`].
+    normalize_config: Option<crate::normalize::GuardConfig>,
+    /// Inbound filter applied to each chunk before rules evaluate it.
+    inbound_filter: Option<crate::filter::Filter>,
+    /// Outbound filter applied to the text returned to the caller.
+    outbound_filter: Option<crate::filter::Filter>,
+    /// Names of filter stages that altered text during the last `feed`.
+    filter_events: Vec<String>,
+    /// Size (in characters) of the carried-over overlap window, if enabled.
+    overlap_window: Option<usize>,
+    /// Trailing characters of already-processed text kept for cross-chunk matching.
+    overlap_tail: String,
+    /// Running byte offset of text observed through [`Self::feed_report`].
+    report_offset: usize,
+    /// Observer notified on every blocking decision produced by `feed`.
+    on_block: Option<BlockObserver>,
+    /// Observer notified on every rewrite decision produced by `feed`.
+    on_rewrite: Option<RewriteObserver>,
+    /// Discard policy applied to `PatternRule`s added via
+    /// [`Self::add_pattern_rule`].
+    pattern_discard_policy: crate::rules::DiscardPolicy,
+    /// Rules whose match overrides an otherwise-blocking decision back to
+    /// `Decision::Allow` for the current chunk, e.g. `@@` lines from
+    /// [`Self::load_filter_list`].
+    exception_rules: Vec<Box<dyn Rule>>,
+    /// Shared Aho-Corasick index over every eligible single-token
+    /// `ForbiddenSequenceRule` in `rules` (see [`SequenceFastPath`] and
+    /// [`Self::ensure_seq_fast_path`]), rebuilt whenever `rules` has grown
+    /// since the last build.
+    seq_fast_path: Option<SequenceFastPath>,
+    /// `rules.len()` at the time `seq_fast_path` was last built.
+    seq_fast_path_len: usize,
+    /// Config this engine was built from, retained so it can round-trip out.
+    #[cfg(feature = "config")]
+    source_config: Option<crate::config::EngineConfig>,
+    /// `(name, aliases)` of every rule loaded via [`Self::load_rule_pack`] /
+    /// [`Self::load_rule_pack_toml`], in load order -- lets a caller look up
+    /// which rule a name or alias refers to for future enable/disable
+    /// tooling.
+    #[cfg(feature = "config")]
+    rule_pack_names: Vec<(String, Vec<String>)>,
+    /// Asynchronous rules evaluated off the hot path via the worker pool.
+    #[cfg(feature = "async")]
+    async_rules: Vec<Box<dyn AsyncRule>>,
+    /// Background worker pool driving async rule evaluation.
+    #[cfg(feature = "async")]
+    worker: WorkerPool,
 }
 
 impl GuardEngine {
@@ -49,10 +359,41 @@ impl GuardEngine {
             score_decay: 0.0,
             rewrite_mode: RewriteMode::FirstWins,
             score_details: Vec::new(),
+            last_match_span: None,
+            last_match_info: None,
+            time_decay: None,
+            clock: None,
+            last_feed_secs: None,
+            normalize_config: None,
+            inbound_filter: None,
+            outbound_filter: None,
+            filter_events: Vec::new(),
+            overlap_window: None,
+            overlap_tail: String::new(),
+            report_offset: 0,
+            on_block: None,
+            on_rewrite: None,
+            pattern_discard_policy: crate::rules::DiscardPolicy::default(),
+            exception_rules: Vec::new(),
+            seq_fast_path: None,
+            seq_fast_path_len: 0,
+            #[cfg(feature = "config")]
+            source_config: None,
+            #[cfg(feature = "config")]
+            rule_pack_names: Vec::new(),
+            #[cfg(feature = "async")]
+            async_rules: Vec::new(),
+            #[cfg(feature = "async")]
+            worker: WorkerPool::new(WorkerConfig::default()),
         }
     }
 
     /// Create an engine with a score threshold
+    ///
+    /// This is the constructor for scoring mode: rules no longer need to be
+    /// individually decisive, since [`Self::feed`] (and [`Self::feed_scored`]
+    /// for the running breakdown) blocks once accumulated scores reach
+    /// `threshold` rather than on the first rule match.
     pub fn with_score_threshold(threshold: u32) -> Self {
         Self {
             score_threshold: Some(threshold),
@@ -68,6 +409,64 @@ impl GuardEngine {
         }
     }
 
+    /// Create an engine with wall-clock (time-based) score decay
+    ///
+    /// Unlike [`Self::with_score_decay`], which only decays on chunks that
+    /// contribute zero score, this decays by elapsed wall-clock time: before
+    /// accumulating each chunk's score, `current_score` is reduced by
+    /// `points_per_second * seconds_since_last_feed` (clamped at zero). This
+    /// turns the threshold into a leaky-bucket rate guard — sustained
+    /// suspicious content crosses it while sparse hits dissipate.
+    ///
+    /// With the `std` feature a [`SystemClock`] is used; inject a custom
+    /// [`Clock`] via [`Self::set_clock`] for deterministic tests.
+    pub fn with_time_decay(points_per_second: f32) -> Self {
+        Self {
+            time_decay: Some(points_per_second.max(0.0)),
+            #[cfg(feature = "std")]
+            clock: Some(Box::new(SystemClock::new())),
+            ..Self::new()
+        }
+    }
+
+    /// Inject the [`Clock`] used for wall-clock decay.
+    ///
+    /// Tests can supply a fake clock to advance time deterministically.
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.clock = Some(clock);
+    }
+
+    /// Apply elapsed-time decay to `current_score` before this chunk scores.
+    ///
+    /// No-op unless time decay is configured and a clock is available.
+    fn apply_time_decay(&mut self) {
+        let (Some(rate), Some(clock)) = (self.time_decay, self.clock.as_ref()) else {
+            return;
+        };
+        let now = clock.now_secs();
+        if let Some(last) = self.last_feed_secs {
+            let elapsed = (now - last).max(0.0);
+            let decayed = (rate as f64 * elapsed) as u32;
+            self.current_score = self.current_score.saturating_sub(decayed);
+        }
+        self.last_feed_secs = Some(now);
+    }
+
+    /// Create an engine that keeps a trailing overlap window of `n` characters
+    ///
+    /// In overlap mode, each `feed` evaluates rules against the carried-over
+    /// tail concatenated with the new chunk, so a forbidden phrase that
+    /// straddles two `feed` calls (e.g. `"ig"` + `"nore previous"`) is still
+    /// caught. Scores and block/rewrite decisions are attributed only to
+    /// matches that extend into the newly-added region, so the carried tail is
+    /// never double-scored. The tail is cleared on [`Self::reset`].
+    pub fn with_overlap_window(n: usize) -> Self {
+        Self {
+            overlap_window: Some(n),
+            ..Self::new()
+        }
+    }
+
     /// Create an engine with rewrite chaining enabled
     pub fn with_rewrite_chain() -> Self {
         Self {
@@ -76,6 +475,272 @@ impl GuardEngine {
         }
     }
 
+    /// Build an engine from a declarative [`EngineConfig`](crate::config::EngineConfig).
+    ///
+    /// The config's schema version is negotiated first (see
+    /// [`EngineConfig::negotiate`](crate::config::EngineConfig::negotiate));
+    /// rules are instantiated by name through `registry`, and the
+    /// scoring/rewrite settings are wired up. The source config is retained so
+    /// it can round-trip back out via [`Self::to_config`].
+    #[cfg(feature = "config")]
+    pub fn from_config(
+        config: crate::config::EngineConfig,
+        registry: &crate::config::RuleRegistry,
+    ) -> Result<Self, crate::config::ConfigError> {
+        let caps = config.negotiate()?;
+
+        let mut engine = Self::new();
+        engine.score_threshold = config.score_threshold;
+        if let Some(decay) = config.score_decay {
+            engine.score_decay = decay.clamp(0.0, 1.0);
+        }
+        // Only honor chained rewrites when this build advertises the capability.
+        if config.rewrite_chain && caps.chained_rewrites {
+            engine.rewrite_mode = RewriteMode::Chain;
+        }
+
+        for rule_cfg in &config.rules {
+            engine.rules.push(registry.build(rule_cfg)?);
+        }
+
+        engine.source_config = Some(config);
+        Ok(engine)
+    }
+
+    /// Return the declarative config this engine was built from, if any.
+    #[cfg(feature = "config")]
+    pub fn to_config(&self) -> Option<crate::config::EngineConfig> {
+        self.source_config.clone()
+    }
+
+    /// Load a declarative rule pack (see [`crate::rules::rulepack`]) from a
+    /// JSON string and add each entry's rule in order.
+    ///
+    /// Unlike [`Self::from_config`], a pack describes only named, optionally
+    /// aliased rules -- not engine-wide settings -- so it composes with
+    /// whatever engine-level config is already in place. Returns the names
+    /// registered, in load order, on success.
+    #[cfg(feature = "config")]
+    pub fn load_rule_pack(
+        &mut self,
+        source: &str,
+    ) -> Result<Vec<String>, crate::rules::rulepack::RulePackError> {
+        let pack = crate::rules::rulepack::RulePack::from_json(source)?;
+        self.register_rule_pack(pack)
+    }
+
+    /// Like [`Self::load_rule_pack`], but parses TOML instead of JSON.
+    #[cfg(feature = "config")]
+    pub fn load_rule_pack_toml(
+        &mut self,
+        source: &str,
+    ) -> Result<Vec<String>, crate::rules::rulepack::RulePackError> {
+        let pack = crate::rules::rulepack::RulePack::from_toml(source)?;
+        self.register_rule_pack(pack)
+    }
+
+    /// Build every entry in `pack` and push it onto [`Self::rules`],
+    /// recording its name/aliases in [`Self::rule_pack_names`].
+    #[cfg(feature = "config")]
+    fn register_rule_pack(
+        &mut self,
+        pack: crate::rules::rulepack::RulePack,
+    ) -> Result<Vec<String>, crate::rules::rulepack::RulePackError> {
+        let named = crate::rules::rulepack::build(&pack)?;
+        let mut names = Vec::new();
+        for entry in named {
+            names.push(entry.name.clone());
+            self.rule_pack_names.push((entry.name, entry.aliases));
+            self.rules.push(entry.rule);
+        }
+        Ok(names)
+    }
+
+    /// The `(name, aliases)` of every rule loaded via [`Self::load_rule_pack`]
+    /// / [`Self::load_rule_pack_toml`], in load order.
+    #[cfg(feature = "config")]
+    pub fn rule_pack_names(&self) -> &[(String, Vec<String>)] {
+        &self.rule_pack_names
+    }
+
+    /// Build a fresh engine from a declarative rule pack string, trying JSON
+    /// first and falling back to TOML if that fails to parse.
+    ///
+    /// This is the one-call convenience for the common case of "I have a
+    /// config string, give me an engine" -- for control over the format, or
+    /// to add a pack's rules to an engine that already has other rules and
+    /// settings, use [`Self::load_rule_pack`] / [`Self::load_rule_pack_toml`]
+    /// directly instead.
+    #[cfg(feature = "config")]
+    pub fn from_config_str(source: &str) -> Result<Self, crate::rules::rulepack::RulePackError> {
+        let mut engine = Self::new();
+        match crate::rules::rulepack::RulePack::from_json(source) {
+            Ok(pack) => {
+                engine.register_rule_pack(pack)?;
+            }
+            Err(_) => {
+                let pack = crate::rules::rulepack::RulePack::from_toml(source)?;
+                engine.register_rule_pack(pack)?;
+            }
+        }
+        Ok(engine)
+    }
+
+    /// Like [`Self::from_config_str`], but reads the rule pack from a file on
+    /// disk, dispatching on its extension: `.toml` parses as TOML, anything
+    /// else (including no extension) as JSON.
+    #[cfg(all(feature = "config", feature = "std"))]
+    pub fn from_config_file(path: &str) -> Result<Self, crate::rules::rulepack::RulePackError> {
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| crate::rules::rulepack::RulePackError::Io(e.to_string()))?;
+
+        let mut engine = Self::new();
+        let pack = if path.ends_with(".toml") {
+            crate::rules::rulepack::RulePack::from_toml(&source)?
+        } else {
+            crate::rules::rulepack::RulePack::from_json(&source)?
+        };
+        engine.register_rule_pack(pack)?;
+        Ok(engine)
+    }
+
+    /// Set the Unicode-evasion normalization pass (see [`crate::normalize`]).
+    ///
+    /// Runs before the inbound filter and every rule, so homoglyph and
+    /// invisible-character evasion (e.g. a forbidden token split apart with
+    /// zero-width joiners, or spelled with Cyrillic lookalikes) is collapsed
+    /// before [`crate::rules::ForbiddenSequenceRule`], [`crate::rules::PatternRule`],
+    /// or any other rule ever sees the text. It is a no-op by default.
+    pub fn set_normalize_config(&mut self, config: crate::normalize::GuardConfig) {
+        self.normalize_config = Some(config);
+    }
+
+    /// Set the inbound sanitization filter.
+    ///
+    /// The inbound filter normalizes each chunk (stripping or escaping
+    /// configured characters/patterns) before any rule sees it, improving
+    /// match reliability against zero-width or markup evasion. It is a no-op
+    /// by default.
+    pub fn set_inbound_filter(&mut self, filter: crate::filter::Filter) {
+        self.inbound_filter = Some(filter);
+    }
+
+    /// Set the outbound sanitization filter.
+    ///
+    /// The outbound filter sanitizes the final text returned to the caller:
+    /// the replacement of a `Rewrite`, or the forwarded chunk of an `Allow`
+    /// (which is then surfaced as a `Rewrite` carrying the cleaned text). It
+    /// is a no-op by default.
+    pub fn set_outbound_filter(&mut self, filter: crate::filter::Filter) {
+        self.outbound_filter = Some(filter);
+    }
+
+    /// Names of the filter stages that altered text during the last `feed`.
+    ///
+    /// Mirrors [`Self::score_details`]: empty when no filter changed anything.
+    pub fn filter_details(&self) -> &[String] {
+        &self.filter_events
+    }
+
+    /// Register an observer invoked whenever `feed` blocks the stream.
+    ///
+    /// The callback receives the block reason and the accumulated score. It is
+    /// a cross-cutting notification hook — for logging, metrics, or early
+    /// cancellation — and does not affect the returned [`Decision`]. A second
+    /// call replaces the previous observer; observers persist across
+    /// [`Self::reset`].
+    pub fn set_on_block(&mut self, observer: BlockObserver) {
+        self.on_block = Some(observer);
+    }
+
+    /// Register an observer invoked whenever `feed` rewrites the stream.
+    ///
+    /// The callback receives the replacement text and the accumulated score.
+    /// See [`Self::set_on_block`] for the semantics.
+    pub fn set_on_rewrite(&mut self, observer: RewriteObserver) {
+        self.on_rewrite = Some(observer);
+    }
+
+    /// Dispatch a non-Allow decision to the registered observers.
+    fn notify_observers(&mut self, decision: &Decision) {
+        let score = self.current_score;
+        match decision {
+            Decision::Block { reason } => {
+                if let Some(cb) = self.on_block.as_mut() {
+                    cb(reason, score);
+                }
+            }
+            Decision::Rewrite { replacement } => {
+                if let Some(cb) = self.on_rewrite.as_mut() {
+                    cb(replacement, score);
+                }
+            }
+            Decision::Annotate { .. } | Decision::Allow => {}
+        }
+    }
+
+    /// Run the Unicode normalization pass over a chunk, returning
+    /// `(text, changed)`.
+    fn run_normalize(&self, chunk: &str) -> (String, bool) {
+        match &self.normalize_config {
+            Some(config) => crate::normalize::normalize(chunk, config),
+            None => (chunk.to_string(), false),
+        }
+    }
+
+    /// Run the inbound filter over a chunk, returning `(text, changed)`.
+    fn run_inbound(&self, chunk: &str) -> (String, bool) {
+        match &self.inbound_filter {
+            Some(filter) => filter.apply(chunk),
+            None => (chunk.to_string(), false),
+        }
+    }
+
+    /// Apply the outbound filter to the text a decision would forward.
+    ///
+    /// `forwarded` is the inbound-filtered chunk the caller would emit on an
+    /// `Allow`. If the outbound filter alters it, the `Allow` becomes a
+    /// `Rewrite` carrying the sanitized text so the caller never sees the
+    /// unsanitized bytes.
+    fn apply_outbound(&mut self, decision: Decision, forwarded: &str) -> Decision {
+        let Some(filter) = &self.outbound_filter else {
+            return decision;
+        };
+        match decision {
+            Decision::Allow => {
+                let (clean, changed) = filter.apply(forwarded);
+                if changed {
+                    self.filter_events.push("outbound".to_string());
+                    Decision::Rewrite { replacement: clean }
+                } else {
+                    Decision::Allow
+                }
+            }
+            Decision::Rewrite { replacement } => {
+                let (clean, changed) = filter.apply(&replacement);
+                if changed {
+                    self.filter_events.push("outbound".to_string());
+                }
+                Decision::Rewrite { replacement: clean }
+            }
+            // Blocks stop the stream; there is no forwarded text to sanitize.
+            block => block,
+        }
+    }
+
+    /// Build an engine from the text rule DSL (see [`crate::rules::compiler`]).
+    ///
+    /// Each line is compiled into a rule and added in order. A syntax error
+    /// surfaces as a [`CompileError`](crate::rules::compiler::CompileError)
+    /// carrying the offending line and column.
+    pub fn from_rules_str(source: &str) -> Result<Self, crate::rules::compiler::CompileError> {
+        let mut engine = Self::new();
+        for rule in crate::rules::compiler::compile(source)? {
+            engine.rules.push(rule);
+        }
+        Ok(engine)
+    }
+
     /// Add a rule to the engine
     ///
     /// Rules are evaluated in the order they are added.
@@ -84,49 +749,259 @@ impl GuardEngine {
         self.rules.push(rule);
     }
 
-    /// Process a chunk of text through all rules
+    /// Add a `PatternRule`, applying this engine's pattern discard policy
+    /// (see [`Self::set_discard_policy`]) so it doesn't keep its resolved
+    /// matcher live indefinitely.
+    pub fn add_pattern_rule(&mut self, rule: crate::rules::PatternRule) {
+        self.rules.push(Box::new(
+            rule.with_discard_policy(self.pattern_discard_policy),
+        ));
+    }
+
+    /// Add many forbidden phrases as a single rule sharing one Aho-Corasick
+    /// automaton, instead of one [`crate::rules::ForbiddenSequenceRule`] per
+    /// phrase.
     ///
-    /// # Arguments
+    /// Each rule in [`Self::rules`] is scanned independently on every
+    /// `feed`, so guarding against dozens of multi-token, gap-allowing
+    /// phrases with one `ForbiddenSequenceRule` apiece costs O(phrases ×
+    /// text) per chunk. This instead builds one
+    /// [`crate::rules::ForbiddenSetRule`] (gap-allowing mode, so behavior
+    /// matches `ForbiddenSequenceRule` including the underlying per-phrase
+    /// cursor/stop-word handling) and registers it as the single rule doing
+    /// the scanning.
     ///
-    /// * `chunk` - The next piece of text to inspect
+    /// A fully shared, O(text)-regardless-of-phrase-count automaton (the
+    /// strict/consecutive path) is available too -- see
+    /// [`crate::rules::ForbiddenSetRule::strict`] -- for callers who don't
+    /// need gaps between tokens. `evaluate_chunk` also drives *every*
+    /// single-token, block-mode `ForbiddenSequenceRule` added normally
+    /// through [`Self::add_rule`] off that same kind of shared automaton
+    /// automatically -- see [`Self::ensure_seq_fast_path`] -- so a plain
+    /// forbidden-word list doesn't need this method at all to get O(text)
+    /// scanning; it only helps for genuinely multi-token, gap-allowing
+    /// phrases, which don't reduce to a single shared pass.
     ///
-    /// # Returns
+    /// There is no equivalent for [`crate::rules::PatternRule`]: its presets
+    /// (email, URL, IPv4, credit card) are structural shapes, not literal
+    /// phrases, so they don't fit an Aho-Corasick trie, and compiling them
+    /// into a `regex::RegexSet` would mean taking a hard `regex` dependency
+    /// for the engine's default rule type -- the opposite of the zero-
+    /// dependency, hand-coded-matcher design `PatternRule`'s module docs
+    /// describe. Callers who want regex-based matching at scale and are
+    /// fine with that dependency should reach for
+    /// [`crate::rules::RegexRule`] (behind the optional `regex` feature)
+    /// and compile their own `regex::RegexSet` directly, rather than this
+    /// engine doing it implicitly for every `PatternRule`.
+    pub fn add_forbidden_phrases<S: AsRef<str>>(
+        &mut self,
+        phrase_tokens: Vec<Vec<S>>,
+        reason: &str,
+    ) {
+        self.rules.push(Box::new(crate::rules::ForbiddenSetRule::with_gaps(
+            phrase_tokens,
+            reason,
+        )));
+    }
+
+    /// Rebuild the shared single-token index (see [`SequenceFastPath`]) if
+    /// `rules` has grown since the last build.
     ///
-    /// A `Decision` indicating whether to allow, block, or rewrite
+    /// Scans `rules` for every
+    /// [`ForbiddenSequenceRule`](crate::rules::ForbiddenSequenceRule) eligible
+    /// for the single-token fast path (see
+    /// [`ForbiddenSequenceRule::as_single_literal_block`](crate::rules::ForbiddenSequenceRule::as_single_literal_block))
+    /// and compiles their literal tokens into one shared automaton, so
+    /// `evaluate_chunk` can scan a chunk once for all of them instead of
+    /// once per rule. Outside [`Self::reload_rules`] rules are only ever
+    /// appended, so comparing `rules.len()` against the length at the last
+    /// build is enough to detect staleness; `reload_rules` clears the index
+    /// itself to force a rebuild.
+    fn ensure_seq_fast_path(&mut self) {
+        if self.seq_fast_path.is_some() && self.seq_fast_path_len == self.rules.len() {
+            return;
+        }
+
+        let mut phrases: Vec<String> = Vec::new();
+        let mut rule_indices: Vec<usize> = Vec::new();
+        for (i, rule) in self.rules.iter().enumerate() {
+            if let Some(seq) = rule
+                .as_any()
+                .downcast_ref::<crate::rules::ForbiddenSequenceRule>()
+            {
+                if let Some(token) = seq.as_single_literal_block() {
+                    phrases.push(token.to_string());
+                    rule_indices.push(i);
+                }
+            }
+        }
+
+        self.seq_fast_path = if phrases.is_empty() {
+            None
+        } else {
+            Some(SequenceFastPath {
+                automaton: crate::rules::ahocorasick::AhoCorasick::build(&phrases, false),
+                rule_indices,
+            })
+        };
+        self.seq_fast_path_len = self.rules.len();
+    }
+
+    /// Set the discard policy applied to `PatternRule`s added through
+    /// [`Self::add_pattern_rule`].
     ///
-    /// # Behavior
+    /// Rules added directly via [`Self::add_rule`] are unaffected.
+    pub fn set_discard_policy(&mut self, policy: crate::rules::DiscardPolicy) {
+        self.pattern_discard_policy = policy;
+    }
+
+    /// Builder-style variant of [`Self::set_discard_policy`].
+    pub fn with_discard_policy(mut self, policy: crate::rules::DiscardPolicy) -> Self {
+        self.set_discard_policy(policy);
+        self
+    }
+
+    /// Compile a boolean expression (see [`crate::rules::expr`]) and add it
+    /// as a single composite rule.
     ///
-    /// - If the engine has been stopped by a previous Block decision,
-    ///   returns Block immediately without processing
-    /// - Otherwise, feeds the chunk to each rule in order
-    /// - Returns the first non-Allow decision
-    /// - If all rules return Allow, returns Allow
-    pub fn feed(&mut self, chunk: &str) -> Decision {
-        // Once stopped, remain stopped
+    /// A compile error surfaces as an
+    /// [`ExprError`](crate::rules::expr::ExprError) carrying the byte offset
+    /// of the offending token.
+    pub fn add_expression(&mut self, source: &str) -> Result<(), crate::rules::expr::ExprError> {
+        let rule = crate::rules::expr::compile(source)?;
+        self.rules.push(Box::new(rule));
+        Ok(())
+    }
+
+    /// Add a rule whose match forces [`Self::feed`] to return
+    /// `Decision::Allow` for the current chunk even if another rule would
+    /// have blocked it, the way an `@@` filter punches a hole in a network
+    /// blocker's broader block rules.
+    ///
+    /// Exception rules are fed on every call alongside the ordinary rules
+    /// (so their own cross-chunk buffering stays in sync), but never
+    /// themselves cause a block -- only an override back to `Allow`.
+    ///
+    /// See [`Self::load_filter_list`] for the common case of loading these
+    /// from an Adblock-Plus-style filter list's `@@` lines.
+    pub fn add_exception_rule(&mut self, rule: Box<dyn Rule>) {
+        self.exception_rules.push(rule);
+    }
+
+    /// Load an Adblock-Plus-style filter list (see
+    /// [`crate::rules::filterlist`]).
+    ///
+    /// Plain lines compile into ordinary blocking rules; `@@`-prefixed
+    /// lines compile into exception rules added via
+    /// [`Self::add_exception_rule`].
+    pub fn load_filter_list(&mut self, source: &str) {
+        let list = crate::rules::filterlist::parse(source);
+        for rule in list.rules {
+            self.rules.push(rule);
+        }
+        for rule in list.exceptions {
+            self.exception_rules.push(rule);
+        }
+    }
+
+    /// Wrap `rule` so a match tags the stream with `marker` instead of
+    /// blocking it, and add it like any other rule.
+    ///
+    /// The original chunk keeps flowing and the stream is never stopped;
+    /// `feed` returns [`Decision::Annotate`] instead of `Allow` for the
+    /// chunk that triggered it. This pairs naturally with score-decay:
+    /// borderline content below the block threshold can surface as a
+    /// warning tag instead of an all-or-nothing block.
+    pub fn add_annotate_rule(&mut self, marker: &str, rule: Box<dyn Rule>) {
+        self.rules.push(Box::new(AnnotateRule {
+            inner: rule,
+            marker: marker.to_string(),
+        }));
+    }
+
+    /// Feed `text` to every exception rule and, if any of them matched,
+    /// override an otherwise-blocking `decision` back to `Decision::Allow`.
+    ///
+    /// Exception rules are always fed (never short-circuited), so their
+    /// internal buffers stay current across chunks regardless of whether
+    /// the ordinary rules blocked this particular chunk.
+    fn apply_exceptions(&mut self, decision: Decision, text: &str) -> Decision {
+        let mut excepted = false;
+        for rule in &mut self.exception_rules {
+            if !rule.feed(text).is_allow() {
+                excepted = true;
+            }
+        }
+        if excepted && decision.is_block() {
+            self.stopped = false;
+            Decision::Allow
+        } else {
+            decision
+        }
+    }
+
+    /// Configure the background worker pool used by [`Self::feed_async`].
+    ///
+    /// The `backlog` and `capacity` follow the same naming as the
+    /// external-writer config: `backlog` bounds the queue depth and
+    /// `capacity` bounds how many async rules evaluate concurrently.
+    #[cfg(feature = "async")]
+    pub fn with_worker_pool(config: WorkerConfig) -> Self {
+        Self {
+            worker: WorkerPool::new(config),
+            ..Self::new()
+        }
+    }
+
+    /// Add an asynchronous rule evaluated off the hot path.
+    ///
+    /// Async rules participate in [`Self::feed_async`] only; they are not
+    /// consulted by the synchronous [`Self::feed`].
+    #[cfg(feature = "async")]
+    pub fn add_async_rule(&mut self, rule: Box<dyn AsyncRule>) {
+        self.async_rules.push(rule);
+    }
+
+    /// Asynchronously process a chunk through all async rules.
+    ///
+    /// Each rule is dispatched onto the bounded worker pool and evaluated
+    /// concurrently. The resulting decisions are merged with the same
+    /// first-block / scoring / rewrite-chain semantics as [`Self::feed`]:
+    ///
+    /// - once stopped, further chunks return `Block`
+    /// - empty chunks are allowed
+    /// - in scoring mode (threshold or decay) scores accumulate across all
+    ///   rules and a threshold breach blocks
+    /// - otherwise the first `Block` wins; rewrites chain or first-wins per
+    ///   [`RewriteMode`]
+    #[cfg(feature = "async")]
+    pub async fn feed_async(&mut self, chunk: &str) -> Decision {
         if self.stopped {
             return Decision::Block {
                 reason: "stream already blocked".to_string(),
             };
         }
 
-        // Empty chunks are always allowed
         if chunk.is_empty() {
             return Decision::Allow;
         }
 
-        // Track scores and rewrites for this chunk
+        self.apply_time_decay();
+
+        let decisions = self.worker.dispatch(&mut self.async_rules, chunk).await;
+
+        // Pair each decision with its rule's score and name, then merge.
         let mut chunk_score = 0u32;
         let mut text = chunk.to_string();
         let mut has_rewrite = false;
         let mut first_block: Option<Decision> = None;
+        let mut first_annotation: Option<Decision> = None;
         self.score_details.clear();
 
-        // Evaluate ALL rules to accumulate scores
-        for rule in &mut self.rules {
-            let decision = rule.feed(&text);
+        let scoring_mode = self.score_threshold.is_some() || self.score_decay > 0.0;
+
+        for (rule, decision) in self.async_rules.iter().zip(decisions.into_iter()) {
             let rule_score = rule.last_score();
-            
-            // Always accumulate scores from all rules
             if rule_score > 0 {
                 chunk_score += rule_score;
                 self.score_details.push((rule.name().to_string(), rule_score));
@@ -135,68 +1010,429 @@ impl GuardEngine {
             match decision {
                 Decision::Allow => continue,
                 Decision::Block { .. } => {
-                    // In scoring mode (threshold or decay configured), don't stop on individual blocks
-                    let scoring_mode = self.score_threshold.is_some() || self.score_decay > 0.0;
                     if !scoring_mode && first_block.is_none() {
                         first_block = Some(decision);
                     }
                 }
                 Decision::Rewrite { replacement } => {
                     if self.rewrite_mode == RewriteMode::Chain {
-                        // Chain mode: apply rewrite and continue to next rule
                         text = replacement;
                         has_rewrite = true;
                     } else if first_block.is_none() {
-                        // First-wins mode: remember first rewrite, but continue evaluating
                         first_block = Some(Decision::Rewrite { replacement });
                     }
                 }
+                Decision::Annotate { .. } => {
+                    if first_annotation.is_none() {
+                        first_annotation = Some(decision);
+                    }
+                }
             }
         }
 
-        // Update score after evaluating all rules
         self.current_score += chunk_score;
 
-        // Apply score decay if configured (only if no new scores this chunk)
         if self.score_decay > 0.0 && chunk_score == 0 && self.current_score > 0 {
             self.current_score = (self.current_score as f32 * (1.0 - self.score_decay)) as u32;
         }
 
-        // Check if score threshold is exceeded
         if let Some(threshold) = self.score_threshold {
             if self.current_score >= threshold {
                 self.stopped = true;
                 return Decision::Block {
-                    reason: format!("score threshold exceeded: {} >= {}", self.current_score, threshold),
+                    reason: format!(
+                        "score threshold exceeded: {} >= {}",
+                        self.current_score, threshold
+                    ),
                 };
             }
         }
 
-        // Check if we had a blocking decision (only if no threshold, or threshold not the reason)
         if let Some(block_decision) = first_block {
             self.stopped = true;
             return block_decision;
         }
 
-        // Check if score threshold is exceeded
-        if let Some(threshold) = self.score_threshold {
-            if self.current_score >= threshold {
-                self.stopped = true;
-                return Decision::Block {
-                    reason: format!("score threshold exceeded: {} >= {}", self.current_score, threshold),
-                };
-            }
-        }
-
-        // Return chained rewrite if any rewrites occurred
         if has_rewrite {
             Decision::Rewrite { replacement: text }
+        } else if let Some(annotation) = first_annotation {
+            annotation
         } else {
             Decision::Allow
         }
     }
 
-    /// Reset the engine and all rules
+    /// Process a chunk of text through all rules
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk` - The next piece of text to inspect
+    ///
+    /// # Returns
+    ///
+    /// A `Decision` indicating whether to allow, block, or rewrite
+    ///
+    /// # Behavior
+    ///
+    /// - If the engine has been stopped by a previous Block decision,
+    ///   returns Block immediately without processing
+    /// - Otherwise, feeds the chunk to each rule in order
+    /// - Returns the first non-Allow decision
+    /// - If all rules return Allow, returns Allow
+    pub fn feed(&mut self, chunk: &str) -> Decision {
+        // Once stopped, remain stopped
+        if self.stopped {
+            return Decision::Block {
+                reason: "stream already blocked".to_string(),
+            };
+        }
+
+        // Empty chunks are always allowed
+        if chunk.is_empty() {
+            return Decision::Allow;
+        }
+
+        // Apply wall-clock decay based on time elapsed since the last feed.
+        self.apply_time_decay();
+
+        self.filter_events.clear();
+
+        // Unicode-evasion normalization: collapse invisible characters and
+        // script homoglyphs before anything else sees the chunk.
+        let (normalized, normalize_changed) = self.run_normalize(chunk);
+        if normalize_changed {
+            self.filter_events.push("normalize".to_string());
+        }
+
+        // Inbound filter: normalize the chunk before any rule sees it.
+        let (filtered, inbound_changed) = self.run_inbound(&normalized);
+        if inbound_changed {
+            self.filter_events.push("inbound".to_string());
+        }
+
+        // In overlap mode, evaluate against the carried tail + chunk so that
+        // matches split across chunk boundaries are caught.
+        let decision = if let Some(n) = self.overlap_window {
+            self.feed_overlap(&filtered, n)
+        } else {
+            self.evaluate_chunk(&filtered)
+        };
+
+        // Exception rules (e.g. `@@` filter lines) can override a block back
+        // to Allow for this chunk.
+        let decision = self.apply_exceptions(decision, &filtered);
+
+        // Outbound filter: sanitize the text returned to the caller.
+        let decision = self.apply_outbound(decision, &filtered);
+
+        // Notify observers of any non-Allow decision (logging/metrics/cancel).
+        self.notify_observers(&decision);
+
+        decision
+    }
+
+    /// Evaluate a chunk against all rules and merge into a single decision.
+    ///
+    /// This is the synchronous core shared by [`Self::feed`]; it applies no
+    /// inbound/outbound filtering of its own.
+    fn evaluate_chunk(&mut self, chunk: &str) -> Decision {
+        // Track scores and rewrites for this chunk
+        let mut chunk_score = 0u32;
+        let mut text = chunk.to_string();
+        let mut has_rewrite = false;
+        let mut first_block: Option<Decision> = None;
+        let mut first_annotation: Option<Decision> = None;
+        self.score_details.clear();
+        self.last_match_span = None;
+        self.last_match_info = None;
+
+        // The fast path scans raw `chunk` once, up front, so it's only valid
+        // while `text` hasn't diverged from `chunk` yet. That holds for
+        // every rule in `RewriteMode::FirstWins` (the loop below never
+        // reassigns `text` in that mode), but not in `Chain` mode, where an
+        // earlier rule's rewrite changes what a later rule must actually
+        // scan -- so the shared pre-scan is skipped entirely there and every
+        // rule falls back to scanning on its own.
+        self.ensure_seq_fast_path();
+        let fast_hits: Vec<usize> = if self.rewrite_mode == RewriteMode::Chain {
+            Vec::new()
+        } else {
+            match &mut self.seq_fast_path {
+                Some(fp) => fp
+                    .automaton
+                    .feed(chunk)
+                    .into_iter()
+                    .map(|phrase_idx| fp.rule_indices[phrase_idx])
+                    .collect(),
+                None => Vec::new(),
+            }
+        };
+        let use_fast_path = self.rewrite_mode != RewriteMode::Chain;
+
+        // Evaluate ALL rules to accumulate scores
+        for (rule_index, rule) in self.rules.iter_mut().enumerate() {
+            let decision = if use_fast_path
+                && self
+                    .seq_fast_path
+                    .as_ref()
+                    .map_or(false, |fp| fp.rule_indices.contains(&rule_index))
+            {
+                let matched = fast_hits.contains(&rule_index);
+                match rule
+                    .as_any_mut()
+                    .downcast_mut::<crate::rules::ForbiddenSequenceRule>()
+                {
+                    Some(seq) => seq.feed_shared(&text, matched),
+                    None => rule.feed(&text),
+                }
+            } else {
+                rule.feed(&text)
+            };
+            let rule_score = rule.last_score();
+
+            // Always accumulate scores from all rules
+            if rule_score > 0 {
+                chunk_score += rule_score;
+                self.score_details.push((rule.name().to_string(), rule_score));
+            }
+
+            match decision {
+                Decision::Allow => continue,
+                Decision::Block { .. } => {
+                    // In scoring mode (threshold or decay configured), don't stop on individual blocks
+                    let scoring_mode = self.score_threshold.is_some() || self.score_decay > 0.0;
+                    if !scoring_mode && first_block.is_none() {
+                        self.last_match_span = rule.last_match_span();
+                        self.last_match_info = rule.last_match_info();
+                        first_block = Some(decision);
+                    }
+                }
+                Decision::Rewrite { replacement } => {
+                    if self.rewrite_mode == RewriteMode::Chain {
+                        // Chain mode: apply rewrite and continue to next rule
+                        text = replacement;
+                        has_rewrite = true;
+                    } else if first_block.is_none() {
+                        // First-wins mode: remember first rewrite, but continue evaluating
+                        first_block = Some(Decision::Rewrite { replacement });
+                    }
+                }
+                Decision::Annotate { .. } => {
+                    if first_annotation.is_none() {
+                        first_annotation = Some(decision);
+                    }
+                }
+            }
+        }
+
+        // Update score after evaluating all rules
+        self.current_score += chunk_score;
+
+        // Apply score decay if configured (only if no new scores this chunk)
+        if self.score_decay > 0.0 && chunk_score == 0 && self.current_score > 0 {
+            self.current_score = (self.current_score as f32 * (1.0 - self.score_decay)) as u32;
+        }
+
+        // Check if score threshold is exceeded
+        if let Some(threshold) = self.score_threshold {
+            if self.current_score >= threshold {
+                self.stopped = true;
+                return Decision::Block {
+                    reason: format!("score threshold exceeded: {} >= {}", self.current_score, threshold),
+                };
+            }
+        }
+
+        // Check if we had a blocking decision (only if no threshold, or threshold not the reason)
+        if let Some(block_decision) = first_block {
+            self.stopped = true;
+            return block_decision;
+        }
+
+        // Return chained rewrite if any rewrites occurred
+        if has_rewrite {
+            Decision::Rewrite { replacement: text }
+        } else if let Some(annotation) = first_annotation {
+            annotation
+        } else {
+            Decision::Allow
+        }
+    }
+
+    /// Evaluate a chunk in overlap mode against `tail + chunk`.
+    ///
+    /// Rules are scanned over the combined window, but a match that is wholly
+    /// contained in the carried-over tail (i.e. it already fired on the tail
+    /// alone last time) is suppressed, and its score is subtracted, so the
+    /// tail is never double-counted. After evaluation the last `n` characters
+    /// of the combined text are retained as the new tail, respecting UTF-8
+    /// boundaries.
+    fn feed_overlap(&mut self, chunk: &str, n: usize) -> Decision {
+        let tail = core::mem::take(&mut self.overlap_tail);
+        let combined = format!("{}{}", tail, chunk);
+
+        let mut chunk_score = 0u32;
+        let mut text = combined.clone();
+        let mut has_rewrite = false;
+        let mut first_block: Option<Decision> = None;
+        let mut first_annotation: Option<Decision> = None;
+        self.score_details.clear();
+        self.last_match_span = None;
+        self.last_match_info = None;
+
+        let scoring_mode = self.score_threshold.is_some() || self.score_decay > 0.0;
+
+        for rule in &mut self.rules {
+            // Baseline: does this rule already fire on the carried tail alone?
+            rule.reset();
+            let tail_fired = if tail.is_empty() {
+                false
+            } else {
+                !rule.feed(&tail).is_allow()
+            };
+            let tail_score = rule.last_score();
+
+            // Re-scan the full window from a clean state.
+            rule.reset();
+            let decision = rule.feed(&text);
+            let rule_score = rule.last_score();
+
+            // Attribute only the contribution not already covered by the tail.
+            let new_score = rule_score.saturating_sub(if tail_fired { tail_score } else { 0 });
+            if new_score > 0 {
+                chunk_score += new_score;
+                self.score_details.push((rule.name().to_string(), new_score));
+            }
+
+            // Suppress matches that did not extend into the new region.
+            if tail_fired {
+                continue;
+            }
+
+            match decision {
+                Decision::Allow => continue,
+                Decision::Block { .. } => {
+                    if !scoring_mode && first_block.is_none() {
+                        self.last_match_span = rule.last_match_span();
+                        self.last_match_info = rule.last_match_info();
+                        first_block = Some(decision);
+                    }
+                }
+                Decision::Rewrite { replacement } => {
+                    if self.rewrite_mode == RewriteMode::Chain {
+                        text = replacement;
+                        has_rewrite = true;
+                    } else if first_block.is_none() {
+                        first_block = Some(Decision::Rewrite { replacement });
+                    }
+                }
+                Decision::Annotate { .. } => {
+                    if first_annotation.is_none() {
+                        first_annotation = Some(decision);
+                    }
+                }
+            }
+        }
+
+        self.current_score += chunk_score;
+
+        if self.score_decay > 0.0 && chunk_score == 0 && self.current_score > 0 {
+            self.current_score = (self.current_score as f32 * (1.0 - self.score_decay)) as u32;
+        }
+
+        // Retain the last `n` characters of the combined text as the new tail.
+        self.overlap_tail = keep_last_chars(&combined, n);
+
+        if let Some(threshold) = self.score_threshold {
+            if self.current_score >= threshold {
+                self.stopped = true;
+                return Decision::Block {
+                    reason: format!(
+                        "score threshold exceeded: {} >= {}",
+                        self.current_score, threshold
+                    ),
+                };
+            }
+        }
+
+        if let Some(block_decision) = first_block {
+            self.stopped = true;
+            return block_decision;
+        }
+
+        if has_rewrite {
+            // Map the replacement back so only the portion corresponding to
+            // the current chunk (not the carried tail) is returned.
+            Decision::Rewrite {
+                replacement: strip_tail_prefix(&tail, text),
+            }
+        } else if let Some(annotation) = first_annotation {
+            annotation
+        } else {
+            Decision::Allow
+        }
+    }
+
+    /// Force evaluation of any pending overlap tail and clear it; when there
+    /// is no pending tail, let every rule flush whatever it's withholding
+    /// instead (e.g. a [`PatternRule`](crate::rules::PatternRule) in redact
+    /// mode holding back text that could still complete a match -- see
+    /// [`Rule::flush`]). While a tail is pending, the rules' own
+    /// [`Rule::flush`] is not called.
+    ///
+    /// Useful at end of stream, or as a timed flush on an idle one, so a
+    /// stalled producer cannot hide a partial match sitting in a tail or
+    /// rule buffer indefinitely. Returns the resulting decision, or `Allow`
+    /// if nothing was withheld anywhere.
+    pub fn flush(&mut self) -> Decision {
+        if self.stopped {
+            return Decision::Block {
+                reason: "stream already blocked".to_string(),
+            };
+        }
+        let tail = core::mem::take(&mut self.overlap_tail);
+        if tail.is_empty() {
+            // No overlap tail (or overlap mode is off) -- give each rule a
+            // chance to flush whatever else it's withholding instead (e.g. a
+            // redact-mode `PatternRule`'s in-progress match candidate).
+            let mut first_annotation: Option<Decision> = None;
+            for rule in &mut self.rules {
+                let decision = rule.flush();
+                if decision.is_block() {
+                    self.stopped = true;
+                    return decision;
+                }
+                if let Decision::Rewrite { replacement } = decision {
+                    if replacement.is_empty() {
+                        continue;
+                    }
+                    return Decision::Rewrite { replacement };
+                }
+                if decision.is_annotate() && first_annotation.is_none() {
+                    first_annotation = Some(decision);
+                }
+            }
+            return first_annotation.unwrap_or(Decision::Allow);
+        }
+        let mut first_annotation: Option<Decision> = None;
+        for rule in &mut self.rules {
+            rule.reset();
+            let decision = rule.feed(&tail);
+            if decision.is_block() {
+                self.stopped = true;
+                return decision;
+            }
+            if let Decision::Rewrite { replacement } = decision {
+                return Decision::Rewrite { replacement };
+            }
+            if decision.is_annotate() && first_annotation.is_none() {
+                first_annotation = Some(decision);
+            }
+        }
+        first_annotation.unwrap_or(Decision::Allow)
+    }
+
+    /// Reset the engine and all rules
     ///
     /// This clears the stopped state and resets all rule internal state.
     /// Use this when starting a new stream.
@@ -204,9 +1440,75 @@ impl GuardEngine {
         self.stopped = false;
         self.current_score = 0;
         self.score_details.clear();
+        self.last_match_span = None;
+        self.last_match_info = None;
+        self.filter_events.clear();
+        self.overlap_tail.clear();
+        self.report_offset = 0;
+        self.last_feed_secs = None;
         for rule in &mut self.rules {
             rule.reset();
         }
+        for rule in &mut self.exception_rules {
+            rule.reset();
+        }
+        if let Some(fp) = &mut self.seq_fast_path {
+            fp.automaton.reset();
+        }
+        #[cfg(feature = "async")]
+        for rule in &mut self.async_rules {
+            rule.reset();
+        }
+    }
+
+    /// Replace the active rule set while a stream is in flight.
+    ///
+    /// Unlike dropping the engine and building a new one, this preserves
+    /// cross-chunk state for rules that survive the swap: any `new_rules`
+    /// entry whose [`Rule::name`] matches a rule already in [`Self::rules`]
+    /// keeps running the *old* boxed instance (with its accumulated
+    /// buffers) instead of the freshly constructed one passed in. Entries
+    /// with a name not already present are genuinely new, so they're reset
+    /// before being installed -- cheap insurance against a caller handing
+    /// over a rule that was already fed elsewhere. A name present in the old
+    /// set but absent from `new_rules` is simply dropped, same as if it had
+    /// never been added.
+    ///
+    /// This only reconciles [`Self::rules`] (the rules reachable from
+    /// `feed`/`feed_report`); [`Self::exception_rules`] and, with `async`,
+    /// [`Self::async_rules`] are untouched. Name matching only helps when
+    /// rules are given distinct names -- see [`Rule::name`]; the default
+    /// `"unnamed_rule"` collides across every unnamed rule, so an unnamed
+    /// rule is always treated as new.
+    pub fn reload_rules(&mut self, new_rules: Vec<Box<dyn Rule>>) {
+        let mut old_rules: Vec<Box<dyn Rule>> = core::mem::take(&mut self.rules);
+        self.rules = new_rules
+            .into_iter()
+            .map(|mut rule| {
+                let pos = if rule.name() == "unnamed_rule" {
+                    None
+                } else {
+                    old_rules.iter().position(|old| old.name() == rule.name())
+                };
+                if let Some(pos) = pos {
+                    old_rules.remove(pos)
+                } else {
+                    rule.reset();
+                    rule
+                }
+            })
+            .collect();
+        // `rules` may have changed composition without changing length (or
+        // even shrunk), which `ensure_seq_fast_path`'s length check alone
+        // wouldn't catch -- force a rebuild on the next `evaluate_chunk`.
+        self.seq_fast_path = None;
+    }
+
+    /// The engine's rules, consumed -- used by [`crate::watch`] to seed a
+    /// reload from a freshly parsed config without re-running `feed` logic.
+    #[cfg(feature = "watch")]
+    pub(crate) fn into_rules(self) -> Vec<Box<dyn Rule>> {
+        self.rules
     }
 
     /// Check if the engine has been stopped
@@ -228,6 +1530,222 @@ impl GuardEngine {
     pub fn score_details(&self) -> &[(String, u32)] {
         &self.score_details
     }
+
+    /// [`Self::feed`], but returning a [`ScoredDecision`] instead of a bare
+    /// [`Decision`] so a caller can see the running score and per-rule
+    /// breakdown even for chunks that don't (yet) cross
+    /// [`Self::with_score_threshold`]'s threshold -- several individually
+    /// weak signals (a suspicious sequence fragment, a masked-but-present
+    /// email, a borderline keyword) each contribute a little, and this lets
+    /// a caller watch that accumulation happen instead of only learning
+    /// about it on the chunk that finally trips the block.
+    ///
+    /// `score` is this chunk's own contribution (the sum of
+    /// [`Self::score_details`]); `total_score` mirrors
+    /// [`Self::current_score`] after this chunk was applied.
+    pub fn feed_scored(&mut self, chunk: &str) -> ScoredDecision {
+        let decision = self.feed(chunk);
+        let score = self.score_details.iter().map(|(_, s)| *s).sum();
+        ScoredDecision {
+            decision,
+            score,
+            total_score: self.current_score,
+            score_details: self.score_details.clone(),
+        }
+    }
+
+    /// The `(start, end)` char span of the match that produced the most
+    /// recent blocking decision, if the triggering rule tracks match spans
+    /// (e.g. [`crate::rules::ForbiddenSequenceRule`]). `None` if nothing
+    /// blocked, or the rule that blocked doesn't report a span.
+    pub fn last_match_span(&self) -> Option<(usize, usize)> {
+        self.last_match_span
+    }
+
+    /// Structured detail about the match that produced the most recent
+    /// blocking decision -- the rule label, its byte span, and parsed
+    /// sub-components for rules that support it (see
+    /// [`crate::rules::PatternRule`] for email/URL/credit-card matches).
+    /// `None` if nothing blocked, or the rule that blocked doesn't report
+    /// structured match info.
+    pub fn last_match_info(&self) -> Option<&MatchInfo> {
+        self.last_match_info.as_ref()
+    }
+
+    /// Process a chunk and return a structured report of every rule that fired.
+    ///
+    /// This is the diagnostic counterpart to [`Self::feed`]: it applies the
+    /// same normalization, inbound/outbound filtering, exception rules and
+    /// scoring, but instead of discarding information about every
+    /// rule after the first block it records each triggered rule — its name,
+    /// reason or replacement text, score contribution, decision kind, and the
+    /// stream offset at which the chunk completed. The report's `decision`
+    /// preserves `feed`'s precedence (threshold breach, then first block, then
+    /// chained/first-wins rewrite) and `score` mirrors [`Self::current_score`].
+    ///
+    /// Offsets accumulate over the text observed through `feed_report` and are
+    /// cleared by [`Self::reset`]. This method is additive; [`Self::feed`] is
+    /// unaffected.
+    ///
+    /// Unlike `feed`, it feeds every rule the chunk directly: the overlap
+    /// window and the shared single-token automaton are not used, the
+    /// block/rewrite observers are not notified, and
+    /// [`Self::last_match_span`]/[`Self::last_match_info`] are not updated.
+    pub fn feed_report(&mut self, chunk: &str) -> StreamReport {
+        if self.stopped {
+            return StreamReport {
+                matches: Vec::new(),
+                decision: Decision::Block {
+                    reason: "stream already blocked".to_string(),
+                },
+                score: self.current_score,
+            };
+        }
+
+        if chunk.is_empty() {
+            return StreamReport {
+                matches: Vec::new(),
+                decision: Decision::Allow,
+                score: self.current_score,
+            };
+        }
+
+        self.apply_time_decay();
+
+        self.filter_events.clear();
+
+        let (normalized, normalize_changed) = self.run_normalize(chunk);
+        if normalize_changed {
+            self.filter_events.push("normalize".to_string());
+        }
+
+        let (filtered, inbound_changed) = self.run_inbound(&normalized);
+        if inbound_changed {
+            self.filter_events.push("inbound".to_string());
+        }
+
+        self.report_offset += filtered.len();
+        let offset = self.report_offset;
+
+        let mut matches = Vec::new();
+        let mut chunk_score = 0u32;
+        let mut text = filtered.clone();
+        let mut has_rewrite = false;
+        let mut first_block: Option<Decision> = None;
+        let mut first_annotation: Option<Decision> = None;
+        self.score_details.clear();
+
+        let scoring_mode = self.score_threshold.is_some() || self.score_decay > 0.0;
+
+        for rule in &mut self.rules {
+            let decision = rule.feed(&text);
+            let rule_score = rule.last_score();
+
+            if rule_score > 0 {
+                chunk_score += rule_score;
+                self.score_details.push((rule.name().to_string(), rule_score));
+            }
+
+            match decision {
+                Decision::Allow => continue,
+                Decision::Block { reason } => {
+                    matches.push(RuleMatch {
+                        rule: rule.name().to_string(),
+                        detail: reason.clone(),
+                        score: rule_score,
+                        kind: MatchKind::Block,
+                        offset,
+                        matched_text: text.clone(),
+                    });
+                    if !scoring_mode && first_block.is_none() {
+                        first_block = Some(Decision::Block { reason });
+                    }
+                }
+                Decision::Rewrite { replacement } => {
+                    matches.push(RuleMatch {
+                        rule: rule.name().to_string(),
+                        detail: replacement.clone(),
+                        score: rule_score,
+                        kind: MatchKind::Rewrite,
+                        offset,
+                        matched_text: text.clone(),
+                    });
+                    if self.rewrite_mode == RewriteMode::Chain {
+                        text = replacement;
+                        has_rewrite = true;
+                    } else if first_block.is_none() {
+                        first_block = Some(Decision::Rewrite { replacement });
+                    }
+                }
+                Decision::Annotate {
+                    marker,
+                    reason,
+                    score,
+                } => {
+                    matches.push(RuleMatch {
+                        rule: rule.name().to_string(),
+                        detail: reason.clone(),
+                        score: rule_score,
+                        kind: MatchKind::Annotate,
+                        offset,
+                        matched_text: text.clone(),
+                    });
+                    if first_annotation.is_none() {
+                        first_annotation = Some(Decision::Annotate {
+                            marker,
+                            reason,
+                            score,
+                        });
+                    }
+                }
+            }
+        }
+
+        self.current_score += chunk_score;
+
+        if self.score_decay > 0.0 && chunk_score == 0 && self.current_score > 0 {
+            self.current_score = (self.current_score as f32 * (1.0 - self.score_decay)) as u32;
+        }
+
+        let decision = if let Some(threshold) = self.score_threshold {
+            if self.current_score >= threshold {
+                self.stopped = true;
+                Decision::Block {
+                    reason: format!(
+                        "score threshold exceeded: {} >= {}",
+                        self.current_score, threshold
+                    ),
+                }
+            } else if let Some(block_decision) = first_block {
+                self.stopped = true;
+                block_decision
+            } else if has_rewrite {
+                Decision::Rewrite { replacement: text }
+            } else if let Some(annotation) = first_annotation.clone() {
+                annotation
+            } else {
+                Decision::Allow
+            }
+        } else if let Some(block_decision) = first_block {
+            self.stopped = true;
+            block_decision
+        } else if has_rewrite {
+            Decision::Rewrite { replacement: text }
+        } else if let Some(annotation) = first_annotation {
+            annotation
+        } else {
+            Decision::Allow
+        };
+
+        let decision = self.apply_exceptions(decision, &filtered);
+        let decision = self.apply_outbound(decision, &filtered);
+
+        StreamReport {
+            matches,
+            decision,
+            score: self.current_score,
+        }
+    }
 }
 
 impl Default for GuardEngine {
@@ -236,6 +1754,35 @@ impl Default for GuardEngine {
     }
 }
 
+/// Keep the last `n` characters of `text`, respecting UTF-8 boundaries.
+fn keep_last_chars(text: &str, n: usize) -> String {
+    if n == 0 {
+        return String::new();
+    }
+    match text.char_indices().rev().nth(n - 1) {
+        Some((idx, _)) => text[idx..].to_string(),
+        None => text.to_string(),
+    }
+}
+
+/// Strip the portion of `rewritten` that corresponds to the carried `tail`.
+///
+/// The tail was already emitted to the caller on a previous `feed`, so only
+/// the current chunk's share of a rewrite is returned. We drop the longest
+/// common prefix of `tail` and `rewritten` (bounded by the tail length) on a
+/// char boundary.
+fn strip_tail_prefix(tail: &str, rewritten: String) -> String {
+    let mut split = 0;
+    for ((ti, tc), (ri, rc)) in tail.char_indices().zip(rewritten.char_indices()) {
+        if tc != rc {
+            break;
+        }
+        debug_assert_eq!(ti, ri);
+        split = ri + rc.len_utf8();
+    }
+    rewritten[split..].to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -272,6 +1819,39 @@ mod tests {
         }
     }
 
+    // Test rule that counts how many times it was fed, exposed via
+    // `last_score` so tests can observe carried-over state without
+    // downcasting the boxed `dyn Rule`.
+    struct CountingRule {
+        name: &'static str,
+        count: u32,
+    }
+
+    impl CountingRule {
+        fn new(name: &'static str) -> Self {
+            Self { name, count: 0 }
+        }
+    }
+
+    impl Rule for CountingRule {
+        fn feed(&mut self, _chunk: &str) -> Decision {
+            self.count += 1;
+            Decision::Allow
+        }
+
+        fn reset(&mut self) {
+            self.count = 0;
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn last_score(&self) -> u32 {
+            self.count
+        }
+    }
+
     #[test]
     fn test_empty_engine_allows_all() {
         let mut engine = GuardEngine::new();
@@ -327,4 +1907,317 @@ mod tests {
 
         assert_eq!(engine.feed(""), Decision::Allow);
     }
+
+    #[test]
+    fn test_feed_report_collects_all_matches() {
+        let mut engine = GuardEngine::with_score_threshold(100);
+        engine.add_rule(Box::new(TestBlockRule::new()));
+        engine.add_rule(Box::new(TestBlockRule::new()));
+
+        let report = engine.feed_report("bad input");
+        // Both rules fired; feed would only have surfaced one.
+        assert_eq!(report.matches.len(), 2);
+        assert!(report.matches.iter().all(|m| m.kind == MatchKind::Block));
+        assert!(report.matches.iter().all(|m| m.offset == "bad input".len()));
+    }
+
+    #[test]
+    fn test_feed_report_preserves_precedence() {
+        let mut engine = GuardEngine::new();
+        engine.add_rule(Box::new(TestBlockRule::new()));
+
+        let report = engine.feed_report("bad input");
+        assert!(report.decision.is_block());
+        assert!(engine.is_stopped());
+    }
+
+    #[test]
+    fn test_feed_report_captures_matched_text() {
+        let mut engine = GuardEngine::new();
+        engine.add_rule(Box::new(TestBlockRule::new()));
+
+        let report = engine.feed_report("bad input");
+        assert_eq!(report.matches[0].matched_text, "bad input");
+    }
+
+    #[test]
+    fn test_report_to_json_contains_match_fields() {
+        let mut engine = GuardEngine::new();
+        engine.add_rule(Box::new(TestBlockRule::new()));
+
+        let report = engine.feed_report("bad input");
+        let json = report.to_json();
+        assert!(json.starts_with('['));
+        assert!(json.contains("\"rule\":"));
+        assert!(json.contains("\"kind\":\"block\""));
+        assert!(json.contains("\"matched_text\":\"bad input\""));
+    }
+
+    #[test]
+    fn test_report_to_sarif_has_runs_and_results() {
+        let mut engine = GuardEngine::new();
+        engine.add_rule(Box::new(TestBlockRule::new()));
+
+        let report = engine.feed_report("bad input");
+        let sarif = report.to_sarif();
+        assert!(sarif.contains("\"runs\":["));
+        assert!(sarif.contains("\"results\":["));
+        assert!(sarif.contains("\"ruleId\":"));
+    }
+
+    #[test]
+    fn test_overlap_window_catches_split_match() {
+        let mut engine = GuardEngine::with_overlap_window(4);
+        engine.add_rule(Box::new(TestBlockRule::new()));
+
+        // "bad" straddles the chunk boundary "ba" + "d".
+        assert!(engine.feed("ba").is_allow());
+        assert!(engine.feed("d text").is_block());
+    }
+
+    #[test]
+    fn test_overlap_window_cleared_on_reset() {
+        let mut engine = GuardEngine::with_overlap_window(4);
+        engine.add_rule(Box::new(TestBlockRule::new()));
+
+        engine.feed("ba");
+        engine.reset();
+
+        // After reset the carried tail is gone, so the split does not match.
+        assert!(engine.feed("d text").is_allow());
+    }
+
+    #[test]
+    fn test_exception_rule_overrides_block() {
+        let mut engine = GuardEngine::new();
+        engine.add_rule(Box::new(TestBlockRule::new()));
+        engine.add_exception_rule(Box::new(TestBlockRule::new()));
+
+        assert!(engine.feed("bad input").is_allow());
+        assert!(!engine.is_stopped());
+    }
+
+    #[test]
+    fn test_load_filter_list_exception_beats_block() {
+        let mut engine = GuardEngine::new();
+        engine.load_filter_list("badword\n@@badword");
+
+        assert!(engine.feed("this has badword in it").is_allow());
+    }
+
+    #[test]
+    fn test_load_filter_list_blocks_without_exception() {
+        let mut engine = GuardEngine::new();
+        engine.load_filter_list("badword");
+
+        assert!(engine.feed("this has badword in it").is_block());
+    }
+
+    #[test]
+    fn test_annotate_rule_does_not_stop_stream() {
+        let mut engine = GuardEngine::new();
+        engine.add_annotate_rule("profanity", Box::new(TestBlockRule::new()));
+
+        let decision = engine.feed("bad input");
+        assert!(decision.is_annotate());
+        assert!(!engine.is_stopped());
+
+        // The stream keeps flowing: a later chunk is evaluated normally.
+        assert!(engine.feed("more text").is_allow());
+    }
+
+    #[test]
+    fn test_annotate_rule_carries_marker_and_reason() {
+        let mut engine = GuardEngine::new();
+        engine.add_annotate_rule("profanity", Box::new(TestBlockRule::new()));
+
+        match engine.feed("bad input") {
+            Decision::Annotate { marker, reason, .. } => {
+                assert_eq!(marker, "profanity");
+                assert_eq!(reason, "found bad word");
+            }
+            other => panic!("expected Annotate, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_block_rule_takes_precedence_over_annotate_rule() {
+        let mut engine = GuardEngine::new();
+        engine.add_annotate_rule("profanity", Box::new(TestBlockRule::new()));
+        engine.add_rule(Box::new(TestBlockRule::new()));
+
+        assert!(engine.feed("bad input").is_block());
+    }
+
+    #[test]
+    fn test_feed_report_captures_annotation() {
+        let mut engine = GuardEngine::new();
+        engine.add_annotate_rule("profanity", Box::new(TestBlockRule::new()));
+
+        let report = engine.feed_report("bad input");
+        assert!(report.decision.is_annotate());
+        assert_eq!(report.matches[0].kind, MatchKind::Annotate);
+    }
+
+    #[test]
+    fn test_normalize_config_catches_zero_width_split_sequence() {
+        let mut engine = GuardEngine::new();
+        engine.set_normalize_config(crate::normalize::GuardConfig::new().strip_invisible(true));
+        engine.add_rule(Box::new(TestBlockRule::new()));
+
+        // "bad" split with zero-width spaces would never match a raw
+        // substring check, but the normalization pass strips them first.
+        assert!(engine.feed("b\u{200B}a\u{200B}d input").is_block());
+    }
+
+    #[test]
+    fn test_normalize_config_reports_filter_details() {
+        let mut engine = GuardEngine::new();
+        engine.set_normalize_config(crate::normalize::GuardConfig::new().fold_confusables(true));
+        engine.add_rule(Box::new(TestBlockRule::new()));
+
+        // Cyrillic `а` folds to Latin `a`, turning "bаd" into "bad".
+        engine.feed("b\u{0430}d input");
+        assert_eq!(engine.filter_details(), &["normalize".to_string()]);
+    }
+
+    #[test]
+    fn test_no_normalize_config_is_noop() {
+        let mut engine = GuardEngine::new();
+        engine.add_rule(Box::new(TestBlockRule::new()));
+
+        // Without configuring normalization, a zero-width-split token is not
+        // caught -- confirms the pass is opt-in, not always-on.
+        assert!(engine.feed("b\u{200B}a\u{200B}d input").is_allow());
+    }
+
+    #[test]
+    fn test_add_forbidden_phrases_registers_single_shared_rule() {
+        let mut engine = GuardEngine::new();
+        engine.add_forbidden_phrases(
+            vec![vec!["how", "to", "hack"], vec!["steal", "password"]],
+            "threat",
+        );
+
+        assert_eq!(engine.rules.len(), 1);
+        assert!(engine.feed("how to safely hack").is_block());
+    }
+
+    #[test]
+    fn test_add_forbidden_phrases_tracks_each_phrase_independently() {
+        let mut engine = GuardEngine::new();
+        engine.add_forbidden_phrases(
+            vec![vec!["how", "to", "hack"], vec!["steal", "password"]],
+            "threat",
+        );
+
+        assert!(engine.feed("how to ").is_allow());
+        assert!(engine.feed("steal a ").is_allow());
+        assert!(engine.feed("password").is_block());
+    }
+
+    #[test]
+    fn test_single_token_forbidden_sequence_rules_share_one_automaton() {
+        let mut engine = GuardEngine::new();
+        engine.add_rule(Box::new(crate::rules::ForbiddenSequenceRule::with_gaps(vec!["cat"], "pets")));
+        engine.add_rule(Box::new(crate::rules::ForbiddenSequenceRule::with_gaps(vec!["dog"], "pets")));
+
+        assert!(engine.feed("a fish swims").is_allow());
+        assert!(engine.feed("a cat sleeps").is_block());
+    }
+
+    #[test]
+    fn test_single_token_forbidden_sequence_rule_matches_split_across_chunks() {
+        let mut engine = GuardEngine::new();
+        engine.add_rule(Box::new(crate::rules::ForbiddenSequenceRule::with_gaps(vec!["forbidden"], "test")));
+
+        assert!(engine.feed("this is forbi").is_allow());
+        assert!(engine.feed("dden content").is_block());
+    }
+
+    #[test]
+    fn test_multi_token_forbidden_sequence_rule_still_allows_gaps() {
+        // Only single-token rules are eligible for the shared automaton --
+        // a multi-token rule must keep working exactly as before.
+        let mut engine = GuardEngine::new();
+        engine.add_rule(Box::new(crate::rules::ForbiddenSequenceRule::with_gaps(
+            vec!["how", "to", "hack"],
+            "threat",
+        )));
+
+        assert!(engine.feed("how to safely hack").is_block());
+    }
+
+    #[test]
+    fn test_rewrite_mode_single_token_sequence_rule_not_fast_pathed() {
+        // A rewrite-mode rule needs its own matched span to splice the
+        // replacement in, so it's excluded from the fast path and must keep
+        // working normally.
+        let mut engine = GuardEngine::new();
+        engine.add_rule(Box::new(crate::rules::ForbiddenSequenceRule::new_with_rewrite(
+            vec!["secret"],
+            "[redacted]",
+        )));
+
+        let decision = engine.feed("the secret is out");
+        assert_eq!(decision.rewritten_text(), Some("the [redacted] is out"));
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_from_config_str_builds_engine_from_json() {
+        let json = r#"{"rules":[{"name":"email_pii","kind":"pattern","message":"email found","preset":"email"}]}"#;
+        let mut engine = GuardEngine::from_config_str(json).unwrap();
+        assert!(engine.feed("contact user@example.com").is_block());
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_from_config_str_falls_back_to_toml() {
+        let toml_src = "[[rules]]\nname = \"email_pii\"\nkind = \"pattern\"\nmessage = \"email found\"\npreset = \"email\"\n";
+        let mut engine = GuardEngine::from_config_str(toml_src).unwrap();
+        assert!(engine.feed("contact user@example.com").is_block());
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_from_config_str_invalid_in_both_formats_errors() {
+        assert!(GuardEngine::from_config_str("not json or toml {{{").is_err());
+    }
+
+    #[test]
+    fn test_reload_rules_preserves_state_for_matching_names_and_resets_new_ones() {
+        let mut engine = GuardEngine::new();
+        engine.add_rule(Box::new(CountingRule::new("alpha")));
+        engine.feed("one");
+        engine.feed("two");
+        assert_eq!(engine.rules[0].last_score(), 2);
+
+        engine.reload_rules(vec![
+            Box::new(CountingRule::new("alpha")),
+            Box::new(CountingRule::new("beta")),
+        ]);
+
+        assert_eq!(engine.rules.len(), 2);
+        assert_eq!(engine.rules[0].name(), "alpha");
+        assert_eq!(
+            engine.rules[0].last_score(),
+            2,
+            "surviving rule keeps its accumulated count"
+        );
+        assert_eq!(engine.rules[1].name(), "beta");
+        assert_eq!(engine.rules[1].last_score(), 0, "new rule starts fresh");
+    }
+
+    #[test]
+    fn test_reload_rules_drops_rules_absent_from_the_new_set() {
+        let mut engine = GuardEngine::new();
+        engine.add_rule(Box::new(CountingRule::new("alpha")));
+        engine.add_rule(Box::new(CountingRule::new("gamma")));
+
+        engine.reload_rules(vec![Box::new(CountingRule::new("alpha"))]);
+
+        assert_eq!(engine.rules.len(), 1);
+        assert_eq!(engine.rules[0].name(), "alpha");
+    }
 }
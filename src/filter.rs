@@ -0,0 +1,103 @@
+//! Inbound/outbound sanitization filters
+//!
+//! Filters are normalization stages that run independently of the rule list.
+//! An *inbound* filter cleans a chunk before any rule evaluates it, so
+//! attackers cannot slip a forbidden token past matchers using zero-width
+//! characters or stray markup; an *outbound* filter sanitizes the text that is
+//! ultimately returned to the caller. Both are independent of the scoring
+//! logic and are no-ops unless configured.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// A sanitization filter: a set of characters to strip and substitutions to apply.
+///
+/// Stripping runs first (removing every configured character), then each
+/// replacement is applied in order.
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    /// Characters removed from the text entirely.
+    strip: Vec<char>,
+    /// Literal `(from, to)` substitutions applied after stripping.
+    replace: Vec<(String, String)>,
+}
+
+impl Filter {
+    /// Create an empty filter (a no-op until configured).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Strip the given characters from processed text.
+    pub fn strip_chars(mut self, chars: &[char]) -> Self {
+        self.strip.extend_from_slice(chars);
+        self
+    }
+
+    /// Replace every occurrence of `from` with `to`.
+    pub fn replace(mut self, from: &str, to: &str) -> Self {
+        self.replace.push((from.to_string(), to.to_string()));
+        self
+    }
+
+    /// Whether this filter would never change any input.
+    pub fn is_noop(&self) -> bool {
+        self.strip.is_empty() && self.replace.is_empty()
+    }
+
+    /// Apply the filter to `text`, returning the result and whether it changed.
+    pub fn apply(&self, text: &str) -> (String, bool) {
+        if self.is_noop() {
+            return (text.to_string(), false);
+        }
+
+        let mut out: String = if self.strip.is_empty() {
+            text.to_string()
+        } else {
+            text.chars().filter(|c| !self.strip.contains(c)).collect()
+        };
+
+        for (from, to) in &self.replace {
+            if !from.is_empty() {
+                out = out.replace(from.as_str(), to.as_str());
+            }
+        }
+
+        let changed = out != text;
+        (out, changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_noop_filter_leaves_text_unchanged() {
+        let (out, changed) = Filter::new().apply("hello world");
+        assert_eq!(out, "hello world");
+        assert!(!changed);
+    }
+
+    #[test]
+    fn test_strip_zero_width_characters() {
+        // Zero-width space (U+200B) and zero-width joiner (U+200D).
+        let filter = Filter::new().strip_chars(&['\u{200B}', '\u{200D}']);
+        let (out, changed) = filter.apply("ig\u{200B}no\u{200D}re");
+        assert_eq!(out, "ignore");
+        assert!(changed);
+    }
+
+    #[test]
+    fn test_replace_applies_after_strip() {
+        // The zero-width space hides `<b>` from the replacement until the
+        // strip step has removed it.
+        let filter = Filter::new()
+            .strip_chars(&['\u{200B}'])
+            .replace("<b>", "")
+            .replace("</b>", "");
+        let (out, changed) = filter.apply("<\u{200B}b>secret</b>");
+        assert_eq!(out, "secret");
+        assert!(changed);
+    }
+}
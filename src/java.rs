@@ -1,12 +1,13 @@
 //! Java JNI bindings for StreamGuard
-//! 
+//!
 //! Provides native JNI interface for zero-copy performance from Java
 
 use jni::JNIEnv;
-use jni::objects::{JClass, JObject, JString};
+use jni::JavaVM;
+use jni::objects::{GlobalRef, JClass, JObject, JString};
 use jni::sys::{jlong, jint, jobject};
 use alloc::boxed::Box;
-use alloc::string::String;
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
 use crate::core::Decision;
@@ -14,38 +15,161 @@ use crate::engine::GuardEngine;
 use crate::rules::sequence::ForbiddenSequenceRule;
 use crate::rules::pattern::PatternRule;
 
+/// Java exception raised for any failure crossing the native boundary.
+const EXCEPTION_CLASS: &str = "com/streamguard/StreamGuardException";
+
+/// Run a native method body, turning any `Err` or panic into a thrown Java
+/// exception and returning `default`.
+///
+/// Unwinding a panic across an `extern "system"` boundary is undefined
+/// behavior, so every native entry point that can fail funnels through here
+/// (the infallible constructors and `nativeDestroy` do not): the closure is
+/// run under [`catch_unwind`](std::panic::catch_unwind) and, on failure, a
+/// `StreamGuardException` is thrown and a safe default handle is returned.
+fn guard_jni<T>(
+    env: &mut JNIEnv,
+    default: T,
+    f: impl FnOnce(&mut JNIEnv) -> Result<T, String>,
+) -> T {
+    let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(env)));
+    match outcome {
+        Ok(Ok(value)) => value,
+        Ok(Err(msg)) => {
+            let _ = env.throw_new(EXCEPTION_CLASS, msg);
+            default
+        }
+        Err(payload) => {
+            let _ = env.throw_new(EXCEPTION_CLASS, panic_message(payload));
+            default
+        }
+    }
+}
+
+/// Best-effort extraction of a panic payload's message.
+fn panic_message(payload: Box<dyn core::any::Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "panic in native method".to_string()
+    }
+}
+
+/// Reconstruct a live engine reference from a Java handle, or fail cleanly.
+fn engine_mut<'a>(handle: jlong) -> Result<&'a mut GuardEngine, String> {
+    if handle == 0 {
+        return Err("null engine handle".to_string());
+    }
+    Ok(unsafe { &mut *(handle as *mut GuardEngine) })
+}
+
+/// Reconstruct a shared engine reference from a Java handle, or fail cleanly.
+fn engine_ref<'a>(handle: jlong) -> Result<&'a GuardEngine, String> {
+    if handle == 0 {
+        return Err("null engine handle".to_string());
+    }
+    Ok(unsafe { &*(handle as *const GuardEngine) })
+}
+
+/// Read a (possibly null) `JString` into an owned `String`.
+fn read_string(env: &mut JNIEnv, s: &JString) -> Result<String, String> {
+    if s.is_null() {
+        return Err("null string argument".to_string());
+    }
+    env.get_string(s).map(Into::into).map_err(|e| e.to_string())
+}
+
+/// Read a Java `List<String>` into a vector, rejecting null/empty lists.
+fn read_token_list(env: &mut JNIEnv, tokens: &JObject) -> Result<Vec<String>, String> {
+    if tokens.is_null() {
+        return Err("null token list".to_string());
+    }
+    let list = env.get_list(tokens).map_err(|e| e.to_string())?;
+    let size = list.size(env).map_err(|e| e.to_string())?;
+    if size == 0 {
+        return Err("empty token list".to_string());
+    }
+    let mut token_vec = Vec::with_capacity(size as usize);
+    for i in 0..size {
+        let item = list
+            .get(env, i)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "null token in list".to_string())?;
+        let s = read_string(env, &JString::from(item))?;
+        token_vec.push(s);
+    }
+    Ok(token_vec)
+}
+
 // Convert Rust Decision to Java Decision object
-fn decision_to_jobject<'a>(env: &'a mut JNIEnv, decision: &Decision) -> jobject {
+fn decision_to_jobject(env: &mut JNIEnv, decision: &Decision) -> Result<jobject, String> {
     let class_name = "com/streamguard/Decision";
-    
-    match decision {
-        Decision::Allow => {
-            env.call_static_method(
-                class_name,
-                "allow",
-                "()Lcom/streamguard/Decision;",
-                &[]
-            ).unwrap().l().unwrap().into_raw()
-        }
+
+    let obj = match decision {
+        Decision::Allow => env
+            .call_static_method(class_name, "allow", "()Lcom/streamguard/Decision;", &[])
+            .map_err(|e| e.to_string())?,
         Decision::Block { reason } => {
-            let reason_obj = env.new_string(reason).unwrap();
+            let reason_obj = env.new_string(reason).map_err(|e| e.to_string())?;
             env.call_static_method(
                 class_name,
                 "block",
                 "(Ljava/lang/String;)Lcom/streamguard/Decision;",
-                &[(&reason_obj).into()]
-            ).unwrap().l().unwrap().into_raw()
+                &[(&reason_obj).into()],
+            )
+            .map_err(|e| e.to_string())?
         }
         Decision::Rewrite { replacement } => {
-            let text_obj = env.new_string(replacement).unwrap();
+            let text_obj = env.new_string(replacement).map_err(|e| e.to_string())?;
             env.call_static_method(
                 class_name,
                 "rewrite",
                 "(Ljava/lang/String;)Lcom/streamguard/Decision;",
-                &[(&text_obj).into()]
-            ).unwrap().l().unwrap().into_raw()
+                &[(&text_obj).into()],
+            )
+            .map_err(|e| e.to_string())?
         }
-    }
+        Decision::Annotate {
+            marker,
+            reason,
+            score,
+        } => {
+            let marker_obj = env.new_string(marker).map_err(|e| e.to_string())?;
+            let reason_obj = env.new_string(reason).map_err(|e| e.to_string())?;
+            env.call_static_method(
+                class_name,
+                "annotate",
+                "(Ljava/lang/String;Ljava/lang/String;I)Lcom/streamguard/Decision;",
+                &[(&marker_obj).into(), (&reason_obj).into(), (*score as i32).into()],
+            )
+            .map_err(|e| e.to_string())?
+        }
+    };
+
+    Ok(obj.l().map_err(|e| e.to_string())?.into_raw())
+}
+
+/// Call `onBlock`/`onRewrite` on a registered `com/streamguard/DecisionListener`.
+///
+/// Runs on whatever thread drove `feed` (the JNI call already attached it, but
+/// the closure only carries a `JavaVM`, not the original `JNIEnv`, so it
+/// re-attaches). Errors are swallowed: this is a best-effort notification
+/// fired from inside `GuardEngine::feed` and must not panic or throw back
+/// across the boundary it's itself being called from.
+fn invoke_decision_listener(vm: &JavaVM, listener: &GlobalRef, method: &str, text: &str, score: u32) {
+    let Ok(mut env) = vm.attach_current_thread() else {
+        return;
+    };
+    let Ok(text_obj) = env.new_string(text) else {
+        return;
+    };
+    let _ = env.call_method(
+        listener,
+        method,
+        "(Ljava/lang/String;I)V",
+        &[(&text_obj).into(), (score as jint).into()],
+    );
 }
 
 // GuardEngine JNI methods
@@ -75,54 +199,111 @@ pub extern "system" fn Java_com_streamguard_GuardEngine_nativeFeed(
     handle: jlong,
     chunk: JString,
 ) -> jobject {
-    let engine = unsafe { &mut *(handle as *mut GuardEngine) };
-    let chunk_str: String = env.get_string(&chunk).unwrap().into();
-    let decision = engine.feed(&chunk_str);
-    decision_to_jobject(&mut env, &decision)
+    guard_jni(&mut env, core::ptr::null_mut(), |env| {
+        let engine = engine_mut(handle)?;
+        let chunk_str = read_string(env, &chunk)?;
+        let decision = engine.feed(&chunk_str);
+        decision_to_jobject(env, &decision)
+    })
 }
 
 #[no_mangle]
 pub extern "system" fn Java_com_streamguard_GuardEngine_nativeReset(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _obj: JObject,
     handle: jlong,
 ) {
-    let engine = unsafe { &mut *(handle as *mut GuardEngine) };
-    engine.reset();
+    guard_jni(&mut env, (), |_env| {
+        engine_mut(handle)?.reset();
+        Ok(())
+    })
 }
 
 #[no_mangle]
 pub extern "system" fn Java_com_streamguard_GuardEngine_nativeCurrentScore(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _obj: JObject,
     handle: jlong,
 ) -> jint {
-    let engine = unsafe { &*(handle as *const GuardEngine) };
-    engine.current_score() as jint
+    guard_jni(&mut env, 0, |_env| {
+        Ok(engine_ref(handle)?.current_score() as jint)
+    })
+}
+
+/// Register a `com/streamguard/DecisionListener` to be notified of `Block`
+/// decisions produced by `feed`. A second call replaces the previous listener.
+#[no_mangle]
+pub extern "system" fn Java_com_streamguard_GuardEngine_nativeSetBlockCallback(
+    mut env: JNIEnv,
+    _obj: JObject,
+    handle: jlong,
+    listener: JObject,
+) {
+    guard_jni(&mut env, (), |env| {
+        let engine = engine_mut(handle)?;
+        let vm = env.get_java_vm().map_err(|e| e.to_string())?;
+        let listener_ref = env.new_global_ref(listener).map_err(|e| e.to_string())?;
+        engine.set_on_block(Box::new(move |reason, score| {
+            invoke_decision_listener(&vm, &listener_ref, "onBlock", reason, score);
+        }));
+        Ok(())
+    })
+}
+
+/// Register a `com/streamguard/DecisionListener` to be notified of `Rewrite`
+/// decisions produced by `feed`. A second call replaces the previous listener.
+#[no_mangle]
+pub extern "system" fn Java_com_streamguard_GuardEngine_nativeSetRewriteCallback(
+    mut env: JNIEnv,
+    _obj: JObject,
+    handle: jlong,
+    listener: JObject,
+) {
+    guard_jni(&mut env, (), |env| {
+        let engine = engine_mut(handle)?;
+        let vm = env.get_java_vm().map_err(|e| e.to_string())?;
+        let listener_ref = env.new_global_ref(listener).map_err(|e| e.to_string())?;
+        engine.set_on_rewrite(Box::new(move |replacement, score| {
+            invoke_decision_listener(&vm, &listener_ref, "onRewrite", replacement, score);
+        }));
+        Ok(())
+    })
 }
 
 #[no_mangle]
 pub extern "system" fn Java_com_streamguard_GuardEngine_nativeAddForbiddenSequence(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _obj: JObject,
     handle: jlong,
     rule_handle: jlong,
 ) {
-    let engine = unsafe { &mut *(handle as *mut GuardEngine) };
-    let rule = unsafe { Box::from_raw(rule_handle as *mut ForbiddenSequenceRule) };
-    engine.add_rule(rule);
+    guard_jni(&mut env, (), |_env| {
+        let engine = engine_mut(handle)?;
+        if rule_handle == 0 {
+            return Err("null rule handle".to_string());
+        }
+        let rule = unsafe { Box::from_raw(rule_handle as *mut ForbiddenSequenceRule) };
+        engine.add_rule(rule);
+        Ok(())
+    })
 }
 
 #[no_mangle]
 pub extern "system" fn Java_com_streamguard_GuardEngine_nativeAddPatternRule(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _obj: JObject,
     handle: jlong,
     rule_handle: jlong,
 ) {
-    let engine = unsafe { &mut *(handle as *mut GuardEngine) };
-    let rule = unsafe { Box::from_raw(rule_handle as *mut PatternRule) };
-    engine.add_rule(rule);
+    guard_jni(&mut env, (), |_env| {
+        let engine = engine_mut(handle)?;
+        if rule_handle == 0 {
+            return Err("null rule handle".to_string());
+        }
+        let rule = unsafe { Box::from_raw(rule_handle as *mut PatternRule) };
+        engine.add_rule(rule);
+        Ok(())
+    })
 }
 
 #[no_mangle]
@@ -146,18 +327,12 @@ pub extern "system" fn Java_com_streamguard_ForbiddenSequenceRule_nativeStrict(
     tokens: JObject,
     reason: JString,
 ) -> jlong {
-    let list = env.get_list(&tokens).unwrap();
-    let mut token_vec = Vec::new();
-    
-    for i in 0..list.size(&mut env).unwrap() {
-        let item = list.get(&mut env, i).unwrap().unwrap();
-        let s: String = env.get_string(&JString::from(item)).unwrap().into();
-        token_vec.push(s);
-    }
-    
-    let reason_str: String = env.get_string(&reason).unwrap().into();
-    let rule = Box::new(ForbiddenSequenceRule::strict(token_vec, reason_str.as_str()));
-    Box::into_raw(rule) as jlong
+    guard_jni(&mut env, 0, |env| {
+        let token_vec = read_token_list(env, &tokens)?;
+        let reason_str = read_string(env, &reason)?;
+        let rule = Box::new(ForbiddenSequenceRule::strict(token_vec, reason_str.as_str()));
+        Ok(Box::into_raw(rule) as jlong)
+    })
 }
 
 #[no_mangle]
@@ -167,18 +342,12 @@ pub extern "system" fn Java_com_streamguard_ForbiddenSequenceRule_nativeWithGaps
     tokens: JObject,
     reason: JString,
 ) -> jlong {
-    let list = env.get_list(&tokens).unwrap();
-    let mut token_vec = Vec::new();
-    
-    for i in 0..list.size(&mut env).unwrap() {
-        let item = list.get(&mut env, i).unwrap().unwrap();
-        let s: String = env.get_string(&JString::from(item)).unwrap().into();
-        token_vec.push(s);
-    }
-    
-    let reason_str: String = env.get_string(&reason).unwrap().into();
-    let rule = Box::new(ForbiddenSequenceRule::with_gaps(token_vec, reason_str.as_str()));
-    Box::into_raw(rule) as jlong
+    guard_jni(&mut env, 0, |env| {
+        let token_vec = read_token_list(env, &tokens)?;
+        let reason_str = read_string(env, &reason)?;
+        let rule = Box::new(ForbiddenSequenceRule::with_gaps(token_vec, reason_str.as_str()));
+        Ok(Box::into_raw(rule) as jlong)
+    })
 }
 
 #[no_mangle]
@@ -189,18 +358,16 @@ pub extern "system" fn Java_com_streamguard_ForbiddenSequenceRule_nativeWithScor
     reason: JString,
     score: jint,
 ) -> jlong {
-    let list = env.get_list(&tokens).unwrap();
-    let mut token_vec = Vec::new();
-    
-    for i in 0..list.size(&mut env).unwrap() {
-        let item = list.get(&mut env, i).unwrap().unwrap();
-        let s: String = env.get_string(&JString::from(item)).unwrap().into();
-        token_vec.push(s);
-    }
-    
-    let reason_str: String = env.get_string(&reason).unwrap().into();
-    let rule = Box::new(ForbiddenSequenceRule::new_with_score(token_vec, reason_str.as_str(), score as u32));
-    Box::into_raw(rule) as jlong
+    guard_jni(&mut env, 0, |env| {
+        let token_vec = read_token_list(env, &tokens)?;
+        let reason_str = read_string(env, &reason)?;
+        let rule = Box::new(ForbiddenSequenceRule::new_with_score(
+            token_vec,
+            reason_str.as_str(),
+            score as u32,
+        ));
+        Ok(Box::into_raw(rule) as jlong)
+    })
 }
 
 // PatternRule JNI methods
@@ -210,9 +377,11 @@ pub extern "system" fn Java_com_streamguard_PatternRule_nativeEmail(
     _class: JClass,
     reason: JString,
 ) -> jlong {
-    let reason_str: String = env.get_string(&reason).unwrap().into();
-    let rule = Box::new(PatternRule::email(reason_str.as_str()));
-    Box::into_raw(rule) as jlong
+    guard_jni(&mut env, 0, |env| {
+        let reason_str = read_string(env, &reason)?;
+        let rule = Box::new(PatternRule::email(reason_str.as_str()));
+        Ok(Box::into_raw(rule) as jlong)
+    })
 }
 
 #[no_mangle]
@@ -221,9 +390,11 @@ pub extern "system" fn Java_com_streamguard_PatternRule_nativeEmailStrict(
     _class: JClass,
     reason: JString,
 ) -> jlong {
-    let reason_str: String = env.get_string(&reason).unwrap().into();
-    let rule = Box::new(PatternRule::email_strict(reason_str.as_str()));
-    Box::into_raw(rule) as jlong
+    guard_jni(&mut env, 0, |env| {
+        let reason_str = read_string(env, &reason)?;
+        let rule = Box::new(PatternRule::email_strict(reason_str.as_str()));
+        Ok(Box::into_raw(rule) as jlong)
+    })
 }
 
 #[no_mangle]
@@ -232,9 +403,11 @@ pub extern "system" fn Java_com_streamguard_PatternRule_nativeEmailRewrite(
     _class: JClass,
     replacement: JString,
 ) -> jlong {
-    let replacement_str: String = env.get_string(&replacement).unwrap().into();
-    let rule = Box::new(PatternRule::email_rewrite(replacement_str.as_str()));
-    Box::into_raw(rule) as jlong
+    guard_jni(&mut env, 0, |env| {
+        let replacement_str = read_string(env, &replacement)?;
+        let rule = Box::new(PatternRule::email_rewrite(replacement_str.as_str()));
+        Ok(Box::into_raw(rule) as jlong)
+    })
 }
 
 #[no_mangle]
@@ -243,9 +416,11 @@ pub extern "system" fn Java_com_streamguard_PatternRule_nativeUrl(
     _class: JClass,
     reason: JString,
 ) -> jlong {
-    let reason_str: String = env.get_string(&reason).unwrap().into();
-    let rule = Box::new(PatternRule::url(reason_str.as_str()));
-    Box::into_raw(rule) as jlong
+    guard_jni(&mut env, 0, |env| {
+        let reason_str = read_string(env, &reason)?;
+        let rule = Box::new(PatternRule::url(reason_str.as_str()));
+        Ok(Box::into_raw(rule) as jlong)
+    })
 }
 
 #[no_mangle]
@@ -254,9 +429,11 @@ pub extern "system" fn Java_com_streamguard_PatternRule_nativeUrlRewrite(
     _class: JClass,
     replacement: JString,
 ) -> jlong {
-    let replacement_str: String = env.get_string(&replacement).unwrap().into();
-    let rule = Box::new(PatternRule::url_rewrite(replacement_str.as_str()));
-    Box::into_raw(rule) as jlong
+    guard_jni(&mut env, 0, |env| {
+        let replacement_str = read_string(env, &replacement)?;
+        let rule = Box::new(PatternRule::url_rewrite(replacement_str.as_str()));
+        Ok(Box::into_raw(rule) as jlong)
+    })
 }
 
 #[no_mangle]
@@ -265,9 +442,11 @@ pub extern "system" fn Java_com_streamguard_PatternRule_nativeIpv4(
     _class: JClass,
     reason: JString,
 ) -> jlong {
-    let reason_str: String = env.get_string(&reason).unwrap().into();
-    let rule = Box::new(PatternRule::ipv4(reason_str.as_str()));
-    Box::into_raw(rule) as jlong
+    guard_jni(&mut env, 0, |env| {
+        let reason_str = read_string(env, &reason)?;
+        let rule = Box::new(PatternRule::ipv4(reason_str.as_str()));
+        Ok(Box::into_raw(rule) as jlong)
+    })
 }
 
 #[no_mangle]
@@ -276,9 +455,11 @@ pub extern "system" fn Java_com_streamguard_PatternRule_nativeIpv4Rewrite(
     _class: JClass,
     replacement: JString,
 ) -> jlong {
-    let replacement_str: String = env.get_string(&replacement).unwrap().into();
-    let rule = Box::new(PatternRule::ipv4_rewrite(replacement_str.as_str()));
-    Box::into_raw(rule) as jlong
+    guard_jni(&mut env, 0, |env| {
+        let replacement_str = read_string(env, &replacement)?;
+        let rule = Box::new(PatternRule::ipv4_rewrite(replacement_str.as_str()));
+        Ok(Box::into_raw(rule) as jlong)
+    })
 }
 
 #[no_mangle]
@@ -287,9 +468,11 @@ pub extern "system" fn Java_com_streamguard_PatternRule_nativeCreditCard(
     _class: JClass,
     reason: JString,
 ) -> jlong {
-    let reason_str: String = env.get_string(&reason).unwrap().into();
-    let rule = Box::new(PatternRule::credit_card(reason_str.as_str()));
-    Box::into_raw(rule) as jlong
+    guard_jni(&mut env, 0, |env| {
+        let reason_str = read_string(env, &reason)?;
+        let rule = Box::new(PatternRule::credit_card(reason_str.as_str()));
+        Ok(Box::into_raw(rule) as jlong)
+    })
 }
 
 #[no_mangle]
@@ -298,7 +481,9 @@ pub extern "system" fn Java_com_streamguard_PatternRule_nativeCreditCardRewrite(
     _class: JClass,
     replacement: JString,
 ) -> jlong {
-    let replacement_str: String = env.get_string(&replacement).unwrap().into();
-    let rule = Box::new(PatternRule::credit_card_rewrite(replacement_str.as_str()));
-    Box::into_raw(rule) as jlong
+    guard_jni(&mut env, 0, |env| {
+        let replacement_str = read_string(env, &replacement)?;
+        let rule = Box::new(PatternRule::credit_card_rewrite(replacement_str.as_str()));
+        Ok(Box::into_raw(rule) as jlong)
+    })
 }
@@ -43,6 +43,7 @@
 //!     Decision::Allow => { /* continue */ },
 //!     Decision::Block { reason } => { /* stop stream */ },
 //!     Decision::Rewrite { replacement } => { /* emit replacement */ },
+//!     Decision::Annotate { marker, reason, score } => { /* tag, keep streaming */ },
 //! }
 //! ```
 
@@ -55,8 +56,26 @@ extern crate alloc;
 
 mod core;
 mod engine;
+pub mod filter;
+pub mod normalize;
 pub mod rules;
 
+// Background worker pool for asynchronous rule evaluation
+#[cfg(feature = "async")]
+mod worker;
+
+// Stream/sink adapters guarding async token streams from an event loop
+#[cfg(feature = "async")]
+mod stream;
+
+// Declarative, versioned engine configuration
+#[cfg(feature = "config")]
+pub mod config;
+
+// Hot-reloading a GuardEngine's rules from a config file on disk
+#[cfg(feature = "watch")]
+pub mod watch;
+
 // WASM bindings for browser usage
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
@@ -70,5 +89,19 @@ mod python;
 mod java;
 
 // Re-export core types
-pub use crate::core::{Decision, Rule, ScoredDecision};
-pub use crate::engine::GuardEngine;
+pub use crate::core::{Decision, MatchComponents, MatchInfo, Rule, ScoredDecision};
+pub use crate::engine::{Clock, GuardEngine, MatchKind, RewriteMode, RuleMatch, StreamReport};
+pub use crate::filter::Filter;
+pub use crate::normalize::GuardConfig;
+#[cfg(feature = "std")]
+pub use crate::engine::SystemClock;
+
+#[cfg(feature = "async")]
+pub use crate::core::AsyncRule;
+#[cfg(feature = "async")]
+pub use crate::worker::{TimeoutPolicy, WorkerConfig, WorkerPool};
+#[cfg(feature = "async")]
+pub use crate::stream::{GuardedSink, GuardedSinkError, GuardedStream};
+
+#[cfg(feature = "watch")]
+pub use crate::watch::ConfigWatcher;
@@ -0,0 +1,283 @@
+//! Unicode-evasion normalization applied before any rule sees a chunk
+//!
+//! A forbidden token can be split apart with zero-width characters
+//! (`h‍o‍w t‍o b‍u‍i‍l‍d`, using zero-width joiners) or spelled with
+//! lookalike letters from another script (Cyrillic `А` for Latin `A`), which
+//! sails straight past [`crate::rules::ForbiddenSequenceRule`] and
+//! [`crate::rules::PatternRule`] since both match against raw text. This
+//! module collapses both tricks in one pass, run by
+//! [`crate::GuardEngine::set_normalize_config`] ahead of the inbound filter
+//! and every rule.
+//!
+//! The pass is deliberately narrow rather than a full Unicode
+//! normalization/confusables implementation -- see [`strip_invisible`],
+//! [`fold_width`], and [`fold_confusables`] below -- the same tradeoff
+//! [`crate::rules::sequence`] already makes for its own (separate, rule-local)
+//! width/confusable folding: a complete NFKC decomposition or a full UTS #39
+//! confusables table needs data tables this crate doesn't vendor.
+//!
+//! # Why no cross-chunk buffering
+//!
+//! [`Rule::feed`](crate::Rule::feed) takes `&str`, which Rust already
+//! guarantees is valid UTF-8 -- there is no partial multi-byte sequence to
+//! buffer at this layer, unlike a raw-byte transport would require. What
+//! *can* straddle a chunk boundary is the forbidden token itself (e.g. the
+//! zero-width joiner lands in one chunk and the next letter in another);
+//! that's already handled downstream by
+//! [`crate::GuardEngine::with_overlap_window`], which this pass runs ahead
+//! of, so carried-over text is normalized exactly like new text.
+
+use alloc::string::{String, ToString};
+
+/// Invisible/format characters stripped when [`GuardConfig::strip_invisible`]
+/// is enabled: zero-width space, ZWNJ, ZWJ, BOM, soft hyphen, the Mongolian
+/// vowel separator, and word joiner. The non-ASCII whitespace block
+/// (U+00A0, U+2000-U+200A) is handled separately by [`is_invisible`] since
+/// it's a contiguous range rather than a handful of scattered code points.
+const INVISIBLE_CHARS: [char; 7] = [
+    '\u{200B}', '\u{200C}', '\u{200D}', '\u{FEFF}', '\u{00AD}', '\u{180E}', '\u{2060}',
+];
+
+/// Whether `c` is one of the invisible/format characters or non-ASCII
+/// whitespace characters this pass strips.
+fn is_invisible(c: char) -> bool {
+    INVISIBLE_CHARS.contains(&c) || c == '\u{00A0}' || ('\u{2000}'..='\u{200A}').contains(&c)
+}
+
+/// Remove every invisible/format character (see [`is_invisible`]) from `s`.
+fn strip_invisible(s: &str) -> String {
+    s.chars().filter(|c| !is_invisible(*c)).collect()
+}
+
+/// Fold fullwidth/halfwidth Unicode forms to their ordinary ASCII
+/// equivalents, e.g. `ｈａｃｋ` -> `hack`, `　` (ideographic space) -> ` `.
+///
+/// This is the same narrow, dependency-free subset of NFKC that
+/// [`crate::rules::sequence`]'s own width folding covers -- a full NFKC
+/// implementation needs Unicode decomposition tables this crate doesn't
+/// vendor, so only this (common evasion) case is handled.
+fn fold_width(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            let cp = c as u32;
+            if (0xFF01..=0xFF5E).contains(&cp) {
+                char::from_u32(cp - 0xFEE0).unwrap_or(c)
+            } else if cp == 0x3000 {
+                ' '
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Fold common Cyrillic and Greek lookalike letters to the Latin letter
+/// they're meant to be mistaken for, e.g. `Аpple` (Cyrillic `А`) -> `Apple`.
+///
+/// A static, hand-picked table -- not a general implementation of Unicode
+/// confusable detection (UTS #39), which requires a much larger data table
+/// this crate doesn't vendor. Unlike [`crate::rules::sequence`]'s own
+/// confusable table, this one only folds script lookalikes, not leetspeak
+/// digits -- digit substitution is a token-matching concern for that rule,
+/// not a script-evasion concern for this engine-wide pass.
+fn fold_confusables(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            // Cyrillic
+            'А' => 'A',
+            'В' => 'B',
+            'Е' => 'E',
+            'К' => 'K',
+            'М' => 'M',
+            'Н' => 'H',
+            'О' => 'O',
+            'Р' => 'P',
+            'С' => 'C',
+            'Т' => 'T',
+            'Х' => 'X',
+            'а' => 'a',
+            'е' => 'e',
+            'о' => 'o',
+            'р' => 'p',
+            'с' => 'c',
+            'х' => 'x',
+            'у' => 'y',
+            'і' => 'i',
+            // Greek
+            'Α' => 'A',
+            'Β' => 'B',
+            'Ε' => 'E',
+            'Ζ' => 'Z',
+            'Η' => 'H',
+            'Ι' => 'I',
+            'Κ' => 'K',
+            'Μ' => 'M',
+            'Ν' => 'N',
+            'Ο' => 'O',
+            'Ρ' => 'P',
+            'Τ' => 'T',
+            'Υ' => 'Y',
+            'Χ' => 'X',
+            'ο' => 'o',
+            'ν' => 'v',
+            'υ' => 'u',
+            'ι' => 'i',
+            other => other,
+        })
+        .collect()
+}
+
+/// Configuration for the Unicode-evasion normalization pass (see the module
+/// docs). All three steps default to off, preserving today's behavior; a
+/// caller opts into exactly the defenses it needs via the builder methods.
+///
+/// ```rust
+/// use streamguard::{GuardEngine, GuardConfig};
+///
+/// let mut engine = GuardEngine::new();
+/// engine.set_normalize_config(
+///     GuardConfig::new()
+///         .strip_invisible(true)
+///         .unicode_normalize(true)
+///         .fold_confusables(true),
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GuardConfig {
+    strip_invisible: bool,
+    unicode_normalize: bool,
+    fold_confusables: bool,
+}
+
+impl GuardConfig {
+    /// Create a config with every step disabled (the default).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable every normalization step.
+    pub fn all() -> Self {
+        Self {
+            strip_invisible: true,
+            unicode_normalize: true,
+            fold_confusables: true,
+        }
+    }
+
+    /// Strip zero-width/invisible characters and non-ASCII whitespace.
+    pub fn strip_invisible(mut self, enabled: bool) -> Self {
+        self.strip_invisible = enabled;
+        self
+    }
+
+    /// Fold fullwidth/halfwidth Unicode forms to ASCII (a narrow NFKC subset).
+    pub fn unicode_normalize(mut self, enabled: bool) -> Self {
+        self.unicode_normalize = enabled;
+        self
+    }
+
+    /// Fold common Cyrillic/Greek lookalike letters to Latin.
+    pub fn fold_confusables(mut self, enabled: bool) -> Self {
+        self.fold_confusables = enabled;
+        self
+    }
+
+    /// Whether this config would never change any input.
+    pub fn is_noop(&self) -> bool {
+        !self.strip_invisible && !self.unicode_normalize && !self.fold_confusables
+    }
+}
+
+/// Apply `config`'s enabled steps to `text`, in order: invisible-character
+/// stripping, width folding, then confusable folding -- stripping runs first
+/// so a zero-width character sitting between two halves of a fullwidth or
+/// confusable character never blocks either fold from seeing the complete
+/// character. Returns the result and whether anything changed.
+pub fn normalize(text: &str, config: &GuardConfig) -> (String, bool) {
+    if config.is_noop() {
+        return (text.to_string(), false);
+    }
+
+    let mut out = text.to_string();
+    if config.strip_invisible {
+        out = strip_invisible(&out);
+    }
+    if config.unicode_normalize {
+        out = fold_width(&out);
+    }
+    if config.fold_confusables {
+        out = fold_confusables(&out);
+    }
+
+    let changed = out != text;
+    (out, changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_noop_config_leaves_text_unchanged() {
+        let (out, changed) = normalize("hello world", &GuardConfig::new());
+        assert_eq!(out, "hello world");
+        assert!(!changed);
+    }
+
+    #[test]
+    fn test_strip_invisible_collapses_split_token() {
+        let config = GuardConfig::new().strip_invisible(true);
+        let (out, changed) = normalize("h\u{200B}o\u{200D}w t\u{2060}o", &config);
+        assert_eq!(out, "how to");
+        assert!(changed);
+    }
+
+    #[test]
+    fn test_strip_invisible_removes_non_ascii_whitespace() {
+        let config = GuardConfig::new().strip_invisible(true);
+        let (out, _) = normalize("a\u{00A0}b\u{2003}c", &config);
+        assert_eq!(out, "abc");
+    }
+
+    #[test]
+    fn test_unicode_normalize_folds_fullwidth_form() {
+        let config = GuardConfig::new().unicode_normalize(true);
+        let (out, changed) = normalize("ｈａｃｋ", &config);
+        assert_eq!(out, "hack");
+        assert!(changed);
+    }
+
+    #[test]
+    fn test_fold_confusables_folds_cyrillic_lookalikes() {
+        let config = GuardConfig::new().fold_confusables(true);
+        let (out, changed) = normalize("Аpple", &config);
+        assert_eq!(out, "Apple");
+        assert!(changed);
+    }
+
+    #[test]
+    fn test_fold_confusables_folds_greek_lookalikes() {
+        let config = GuardConfig::new().fold_confusables(true);
+        let (out, changed) = normalize("Χbox", &config);
+        assert_eq!(out, "Xbox");
+        assert!(changed);
+    }
+
+    #[test]
+    fn test_all_steps_compose() {
+        // U+200B (stripped) sits between `h` and Cyrillic `О` (folded to `O`).
+        let (out, changed) = normalize("h\u{200B}Оw", &GuardConfig::all());
+        assert_eq!(out, "hOw");
+        assert!(changed);
+    }
+
+    #[test]
+    fn test_fold_confusables_leaves_leetspeak_digits_untouched() {
+        // Digit substitution is `SequenceConfig::fold_confusables`'s concern,
+        // not this engine-wide pass's -- it only folds script lookalikes.
+        let config = GuardConfig::new().fold_confusables(true);
+        let (out, changed) = normalize("h4ck", &config);
+        assert_eq!(out, "h4ck");
+        assert!(!changed);
+    }
+}
@@ -3,20 +3,116 @@
 //! Provides native Python extension with zero-copy performance
 
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 use alloc::boxed::Box;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
-use crate::core::Decision;
+use crate::core::{Decision, Rule};
 use crate::engine::GuardEngine as RustGuardEngine;
 use crate::rules::sequence::ForbiddenSequenceRule as RustForbiddenSequenceRule;
 use crate::rules::pattern::PatternRule as RustPatternRule;
 
+/// Adapts a Python callable to the native [`Rule`] trait, so a Python user's
+/// detection logic (an ML classifier, a list lookup, etc.) can run inside the
+/// same engine as the built-in rules.
+///
+/// `feed` calls `callback(chunk)` under the GIL and interprets the return
+/// value: `None` or `False` allows the chunk, `True` blocks it with a generic
+/// reason, a string blocks it with that string as the reason, and a dict
+/// `{"rewrite": "..."}` rewrites the chunk to that replacement. Any other
+/// return value is treated as `Allow`. An exception raised by the callback
+/// blocks the chunk with the exception's text as the reason, so a buggy
+/// callback fails closed rather than silently allowing.
+///
+/// `reset` calls the optional `on_reset` callable, if one was given.
+struct PyCallbackRule {
+    callback: PyObject,
+    on_reset: Option<PyObject>,
+    name: String,
+    score: u32,
+    last_decision_score: u32,
+}
+
+impl Rule for PyCallbackRule {
+    fn feed(&mut self, chunk: &str) -> Decision {
+        Python::with_gil(|py| {
+            let result = match self.callback.call1(py, (chunk,)) {
+                Ok(result) => result,
+                Err(err) => {
+                    self.last_decision_score = self.score;
+                    return Decision::Block {
+                        reason: alloc::format!("{} raised an error: {}", self.name, err),
+                    };
+                }
+            };
+            let bound = result.bind(py);
+
+            if bound.is_none() {
+                self.last_decision_score = 0;
+                return Decision::Allow;
+            }
+
+            if let Ok(dict) = bound.downcast::<PyDict>() {
+                if let Ok(Some(item)) = dict.get_item("rewrite") {
+                    if let Ok(replacement) = item.extract::<String>() {
+                        self.last_decision_score = self.score;
+                        return Decision::Rewrite { replacement };
+                    }
+                }
+                self.last_decision_score = 0;
+                return Decision::Allow;
+            }
+
+            if let Ok(reason) = bound.extract::<String>() {
+                self.last_decision_score = self.score;
+                return Decision::Block { reason };
+            }
+
+            if let Ok(matched) = bound.extract::<bool>() {
+                return if matched {
+                    self.last_decision_score = self.score;
+                    Decision::Block {
+                        reason: alloc::format!("{} matched", self.name),
+                    }
+                } else {
+                    self.last_decision_score = 0;
+                    Decision::Allow
+                };
+            }
+
+            self.last_decision_score = 0;
+            Decision::Allow
+        })
+    }
+
+    fn reset(&mut self) {
+        if let Some(on_reset) = &self.on_reset {
+            Python::with_gil(|py| {
+                let _ = on_reset.call0(py);
+            });
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn last_score(&self) -> u32 {
+        self.last_decision_score
+    }
+}
+
 /// Python wrapper for Decision
 #[pyclass(name = "Decision")]
 #[derive(Clone)]
 pub struct PyDecision {
     inner: Decision,
+    /// `(start, end)` char span of the match that caused this decision, if
+    /// the triggering rule tracks spans. Populated from
+    /// [`RustGuardEngine::last_match_span`] by [`PyGuardEngine::feed`]; not
+    /// part of the underlying [`Decision`] itself.
+    span: Option<(usize, usize)>,
 }
 
 #[pymethods]
@@ -32,27 +128,48 @@ impl PyDecision {
     fn is_rewrite(&self) -> bool {
         matches!(self.inner, Decision::Rewrite { .. })
     }
-    
+
+    fn is_annotate(&self) -> bool {
+        matches!(self.inner, Decision::Annotate { .. })
+    }
+
     fn reason(&self) -> Option<String> {
         match &self.inner {
             Decision::Block { reason } => Some(reason.clone()),
             _ => None,
         }
     }
-    
+
     fn rewritten_text(&self) -> Option<String> {
         match &self.inner {
             Decision::Rewrite { replacement } => Some(replacement.clone()),
             _ => None,
         }
     }
-    
+
+    fn marker(&self) -> Option<String> {
+        match &self.inner {
+            Decision::Annotate { marker, .. } => Some(marker.clone()),
+            _ => None,
+        }
+    }
+
+    /// The `(start, end)` char span of the match that caused this decision,
+    /// for precise logging/redaction, or `None` if the triggering rule
+    /// doesn't report spans.
+    fn span(&self) -> Option<(usize, usize)> {
+        self.span
+    }
+
     fn __repr__(&self) -> String {
         match &self.inner {
             Decision::Allow => "Decision(Allow)".to_string(),
             Decision::Block { reason } => alloc::format!("Decision(Block, reason='{}')", reason),
-            Decision::Rewrite { replacement } => alloc::format!("Decision(Rewrite, text='{}...')", 
+            Decision::Rewrite { replacement } => alloc::format!("Decision(Rewrite, text='{}...')",
                 if replacement.len() > 20 { &replacement[..20] } else { replacement }),
+            Decision::Annotate { marker, reason, .. } => {
+                alloc::format!("Decision(Annotate, marker='{}', reason='{}')", marker, reason)
+            }
         }
     }
 }
@@ -198,24 +315,91 @@ impl PyGuardEngine {
     }
     
     fn feed(&mut self, chunk: &str) -> PyDecision {
-        PyDecision {
-            inner: self.inner.feed(chunk),
-        }
+        let inner = self.inner.feed(chunk);
+        let span = self.inner.last_match_span();
+        PyDecision { inner, span }
     }
     
     fn reset(&mut self) {
         self.inner.reset();
     }
-    
+
     fn current_score(&self) -> u32 {
         self.inner.current_score()
     }
+
+    /// The `(rule_name, score)` pairs contributed by the most recent `feed`,
+    /// so a Python caller can see which rule triggered and by how much.
+    fn score_details(&self) -> Vec<(String, u32)> {
+        self.inner.score_details().to_vec()
+    }
+
+    /// Add a rule backed by a Python callable, so native and Python rules can
+    /// run in the same pipeline. `callback(chunk)` is invoked on every `feed`;
+    /// see [`PyCallbackRule`] for how its return value is interpreted.
+    ///
+    /// `name` labels the rule (shown in `score_details`); `score` is the
+    /// amount contributed to the engine's running score on a block or
+    /// rewrite; `on_reset`, if given, is called when the engine resets.
+    #[pyo3(signature = (callback, name=None, score=0, on_reset=None))]
+    fn add_rule(
+        &mut self,
+        callback: PyObject,
+        name: Option<String>,
+        score: u32,
+        on_reset: Option<PyObject>,
+    ) {
+        let rule = PyCallbackRule {
+            callback,
+            on_reset,
+            name: name.unwrap_or_else(|| "python_callback".to_string()),
+            score,
+            last_decision_score: 0,
+        };
+        self.inner.add_rule(Box::new(rule));
+    }
+
+    /// Register a Python callable invoked as `callback(reason, score)` whenever
+    /// `feed` blocks the stream. A second call replaces the previous callback.
+    fn set_on_block(&mut self, callback: PyObject) {
+        self.inner.set_on_block(Box::new(move |reason, score| {
+            Python::with_gil(|py| {
+                let _ = callback.call1(py, (reason, score));
+            });
+        }));
+    }
+
+    /// Register a Python callable invoked as `callback(replacement, score)`
+    /// whenever `feed` rewrites the stream. A second call replaces the
+    /// previous callback.
+    fn set_on_rewrite(&mut self, callback: PyObject) {
+        self.inner.set_on_rewrite(Box::new(move |replacement, score| {
+            Python::with_gil(|py| {
+                let _ = callback.call1(py, (replacement, score));
+            });
+        }));
+    }
     
     fn __repr__(&self) -> String {
-        alloc::format!("GuardEngine(score={}, rules={})", 
+        alloc::format!("GuardEngine(score={}, rules={})",
             self.inner.current_score(),
             self.inner.rule_count())
     }
+
+    /// Build an engine from a declarative JSON config (see
+    /// `streamguard::config::EngineConfig`), using the built-in rule
+    /// registry. Raises `ValueError` on a parse error, an unsupported
+    /// schema version, or an unrecognized/incomplete rule config.
+    #[staticmethod]
+    #[cfg(feature = "config")]
+    fn from_json(json: &str) -> PyResult<Self> {
+        let config = crate::config::EngineConfig::from_json(json)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(alloc::format!("{:?}", e)))?;
+        let registry = crate::config::RuleRegistry::with_builtins();
+        let inner = RustGuardEngine::from_config(config, &registry)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(alloc::format!("{:?}", e)))?;
+        Ok(Self { inner })
+    }
 }
 
 /// Python module definition
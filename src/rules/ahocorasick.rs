@@ -0,0 +1,583 @@
+//! Multi-phrase matching via an Aho-Corasick automaton
+//!
+//! [`ForbiddenSequenceRule`](crate::rules::ForbiddenSequenceRule) tracks one
+//! ordered token list with a single cursor, so guarding against hundreds of
+//! phrases means hundreds of independent rules each re-scanning the stream
+//! from scratch. [`ForbiddenSetRule`] instead compiles every literal phrase
+//! into one automaton (strict/consecutive mode) or a small set of per-phrase
+//! cursors (gap-allowing mode) and advances all of them in one pass per
+//! chunk.
+//!
+//! # Strict mode: Aho-Corasick
+//!
+//! Phrases are built into a trie over their characters (the goto function),
+//! then a breadth-first pass computes each node's failure link (the longest
+//! proper suffix of its path that is also a trie prefix) and output set (its
+//! own terminal phrase plus everything reachable via its failure link).
+//! Streaming `feed` keeps one node index across chunks; each incoming
+//! character follows goto, falling back through failure links on mismatch,
+//! then reports every phrase in the landing node's output set. This is
+//! O(chunk_len) per chunk regardless of phrase count, and a match split
+//! across chunk boundaries just continues from the carried node index.
+//!
+//! Case folding here is ASCII-only (even under the `std` feature), since
+//! automaton transitions are per-character and Unicode simple case folding
+//! can expand one character into several, which would not fit a single
+//! trie edge.
+//!
+//! # Gap-allowing mode
+//!
+//! Each phrase tracks its own progress as a token cursor -- the same idea as
+//! [`ForbiddenSequenceRule`]'s single `state: usize`, just one per phrase
+//! instead of one for the whole rule -- plus its own partial-token buffer; a
+//! configured stop word resets that phrase's cursor. This does not share the
+//! automaton's O(chunk_len) bound (it is still N independent scans), but the
+//! bookkeeping -- which phrases fired in a given `feed` call, the buffer-size
+//! cap -- is centralized so callers configure and query it as one rule.
+//! Because matched text can have gaps, gap-mode matches only ever `Block`;
+//! there is no unambiguous literal span to splice a `Rewrite` replacement
+//! into, unlike strict mode.
+
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::core::{Decision, Rule};
+
+/// Fold a character to a canonical case for case-insensitive matching.
+///
+/// ASCII-only; see the module docs for why per-character Unicode folding
+/// isn't used here.
+fn fold_char(c: char) -> char {
+    c.to_ascii_lowercase()
+}
+
+/// Fold a string the same way, for phrase pre-processing at build time.
+fn fold_str(s: &str) -> String {
+    s.chars().map(fold_char).collect()
+}
+
+/// One trie node in the automaton: its children (goto), failure link, and
+/// the set of phrase indices that match upon reaching it.
+struct Node {
+    children: BTreeMap<char, usize>,
+    fail: usize,
+    output: Vec<usize>,
+}
+
+/// The compiled strict/consecutive automaton over a fixed phrase list.
+///
+/// Crate-visible (rather than private) so [`crate::engine::GuardEngine`] can
+/// reuse it directly for its single-token `ForbiddenSequenceRule` fast path
+/// instead of duplicating the trie/failure-link construction.
+pub(crate) struct AhoCorasick {
+    nodes: Vec<Node>,
+    /// Current node index, carried across `feed` calls.
+    node: usize,
+    /// Original (unfolded) phrase text, indexed the same as `output`.
+    phrases: Vec<String>,
+    case_insensitive: bool,
+}
+
+impl AhoCorasick {
+    pub(crate) fn build(phrases: &[String], case_insensitive: bool) -> Self {
+        let mut nodes = vec![Node {
+            children: BTreeMap::new(),
+            fail: 0,
+            output: Vec::new(),
+        }];
+
+        for (idx, phrase) in phrases.iter().enumerate() {
+            if phrase.is_empty() {
+                continue;
+            }
+            let scan = if case_insensitive {
+                fold_str(phrase)
+            } else {
+                phrase.clone()
+            };
+            let mut cur = 0;
+            for c in scan.chars() {
+                cur = match nodes[cur].children.get(&c) {
+                    Some(&next) => next,
+                    None => {
+                        nodes.push(Node {
+                            children: BTreeMap::new(),
+                            fail: 0,
+                            output: Vec::new(),
+                        });
+                        let next = nodes.len() - 1;
+                        nodes[cur].children.insert(c, next);
+                        next
+                    }
+                };
+            }
+            nodes[cur].output.push(idx);
+        }
+
+        let mut queue: VecDeque<usize> = VecDeque::new();
+        let root_children: Vec<usize> = nodes[0].children.values().copied().collect();
+        for v in root_children {
+            nodes[v].fail = 0;
+            queue.push_back(v);
+        }
+
+        while let Some(u) = queue.pop_front() {
+            let children: Vec<(char, usize)> =
+                nodes[u].children.iter().map(|(&c, &v)| (c, v)).collect();
+            for (c, v) in children {
+                let mut f = nodes[u].fail;
+                while f != 0 && !nodes[f].children.contains_key(&c) {
+                    f = nodes[f].fail;
+                }
+                let link = nodes[f].children.get(&c).copied().unwrap_or(0);
+                nodes[v].fail = if link == v { 0 } else { link };
+                let inherited = nodes[nodes[v].fail].output.clone();
+                nodes[v].output.extend(inherited);
+                queue.push_back(v);
+            }
+        }
+
+        Self {
+            nodes,
+            node: 0,
+            phrases: phrases.to_vec(),
+            case_insensitive,
+        }
+    }
+
+    /// Feed `chunk` through the automaton, returning the phrase indices
+    /// whose output set was reached by any character in it (deduplicated,
+    /// in first-seen order).
+    pub(crate) fn feed(&mut self, chunk: &str) -> Vec<usize> {
+        let mut matched = Vec::new();
+        for raw_c in chunk.chars() {
+            let c = if self.case_insensitive {
+                fold_char(raw_c)
+            } else {
+                raw_c
+            };
+
+            let mut node = self.node;
+            while node != 0 && !self.nodes[node].children.contains_key(&c) {
+                node = self.nodes[node].fail;
+            }
+            node = self.nodes[node].children.get(&c).copied().unwrap_or(0);
+            self.node = node;
+
+            for &idx in &self.nodes[node].output {
+                if !matched.contains(&idx) {
+                    matched.push(idx);
+                }
+            }
+        }
+        matched
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.node = 0;
+    }
+}
+
+/// Per-phrase cursor for gap-allowing matching, mirroring
+/// [`ForbiddenSequenceRule`]'s single `state`/`buffer` pair.
+struct PhraseProgress {
+    tokens: Vec<String>,
+    state: usize,
+    buffer: String,
+}
+
+impl PhraseProgress {
+    fn new(tokens: Vec<String>) -> Self {
+        Self {
+            tokens,
+            state: 0,
+            buffer: String::new(),
+        }
+    }
+
+    /// Advance this phrase's cursor with `chunk`, returning true once every
+    /// token has appeared in order. Resets on any configured stop word.
+    fn advance(&mut self, chunk: &str, case_insensitive: bool, stop_words: &[String]) -> bool {
+        if case_insensitive {
+            self.buffer.push_str(&fold_str(chunk));
+        } else {
+            self.buffer.push_str(chunk);
+        }
+
+        for stop in stop_words {
+            let needle = if case_insensitive {
+                fold_str(stop)
+            } else {
+                stop.clone()
+            };
+            if !needle.is_empty() && self.buffer.contains(needle.as_str()) {
+                self.reset();
+                return false;
+            }
+        }
+
+        loop {
+            if self.state >= self.tokens.len() {
+                return true;
+            }
+            let target = if case_insensitive {
+                fold_str(&self.tokens[self.state])
+            } else {
+                self.tokens[self.state].clone()
+            };
+            if let Some(pos) = self.buffer.find(target.as_str()) {
+                self.state += 1;
+                let after = pos + target.len();
+                self.buffer = self.buffer[after..].to_string();
+                if self.state >= self.tokens.len() {
+                    return true;
+                }
+            } else {
+                break;
+            }
+        }
+
+        // Cut on a char boundary, not a raw byte offset -- this buffer can
+        // hold multibyte UTF-8 text and a byte-offset cut can land
+        // mid-character.
+        let max_chars = self.tokens.iter().map(|t| t.chars().count()).max().unwrap_or(100);
+        let buffer_chars = self.buffer.chars().count();
+        if buffer_chars > max_chars * 2 {
+            let skip = buffer_chars - max_chars;
+            let byte_offset = self
+                .buffer
+                .char_indices()
+                .nth(skip)
+                .map(|(i, _)| i)
+                .unwrap_or(self.buffer.len());
+            self.buffer = self.buffer[byte_offset..].to_string();
+        }
+
+        false
+    }
+
+    fn reset(&mut self) {
+        self.state = 0;
+        self.buffer.clear();
+    }
+}
+
+/// Which matching strategy a [`ForbiddenSetRule`] was built with.
+enum Engine {
+    Strict(AhoCorasick),
+    Gaps {
+        phrases: Vec<PhraseProgress>,
+        /// Human-readable label per phrase (its tokens joined with spaces),
+        /// for reporting and rewrite lookups.
+        labels: Vec<String>,
+        stop_words: Vec<String>,
+        case_insensitive: bool,
+    },
+}
+
+/// A rule that matches many literal phrases in a single pass per chunk.
+///
+/// See the module docs for the strict (Aho-Corasick) vs gap-allowing
+/// matching strategies.
+pub struct ForbiddenSetRule {
+    engine: Engine,
+    reason: String,
+    /// Rewrite replacement text; only consulted in strict mode (see module
+    /// docs for why gap mode has no unambiguous span to splice).
+    replacement: Option<String>,
+    score: u32,
+    last_decision_score: u32,
+}
+
+impl ForbiddenSetRule {
+    /// Build a strict rule: phrases must appear as exact, consecutive
+    /// substrings, matched case-sensitively.
+    pub fn strict<S: AsRef<str>>(phrases: Vec<S>, reason: &str) -> Self {
+        Self::new_strict(phrases, reason, false)
+    }
+
+    /// Like [`Self::strict`], but ASCII-case-folds both phrases and input.
+    pub fn strict_case_insensitive<S: AsRef<str>>(phrases: Vec<S>, reason: &str) -> Self {
+        Self::new_strict(phrases, reason, true)
+    }
+
+    /// Build a strict rule that rewrites (rather than blocks) every matched
+    /// phrase to `replacement`.
+    pub fn strict_rewrite<S: AsRef<str>>(phrases: Vec<S>, replacement: &str) -> Self {
+        let mut rule = Self::new_strict(phrases, "rewrite forbidden phrase", false);
+        rule.replacement = Some(replacement.to_string());
+        rule
+    }
+
+    fn new_strict<S: AsRef<str>>(phrases: Vec<S>, reason: &str, case_insensitive: bool) -> Self {
+        let phrase_strings: Vec<String> = phrases.iter().map(|s| s.as_ref().to_string()).collect();
+        Self {
+            engine: Engine::Strict(AhoCorasick::build(&phrase_strings, case_insensitive)),
+            reason: reason.to_string(),
+            replacement: None,
+            score: 0,
+            last_decision_score: 0,
+        }
+    }
+
+    /// Build a gap-allowing rule: each phrase is its own ordered token list,
+    /// matched case-sensitively, with no stop words.
+    pub fn with_gaps<S: AsRef<str>>(phrase_tokens: Vec<Vec<S>>, reason: &str) -> Self {
+        Self::new_gaps(phrase_tokens, reason, false)
+    }
+
+    /// Like [`Self::with_gaps`], but ASCII-case-folds tokens and input.
+    pub fn with_gaps_case_insensitive<S: AsRef<str>>(
+        phrase_tokens: Vec<Vec<S>>,
+        reason: &str,
+    ) -> Self {
+        Self::new_gaps(phrase_tokens, reason, true)
+    }
+
+    fn new_gaps<S: AsRef<str>>(
+        phrase_tokens: Vec<Vec<S>>,
+        reason: &str,
+        case_insensitive: bool,
+    ) -> Self {
+        let mut phrases = Vec::with_capacity(phrase_tokens.len());
+        let mut labels = Vec::with_capacity(phrase_tokens.len());
+        for tokens in phrase_tokens {
+            let token_strings: Vec<String> = tokens.iter().map(|t| t.as_ref().to_string()).collect();
+            labels.push(token_strings.join(" "));
+            phrases.push(PhraseProgress::new(token_strings));
+        }
+        Self {
+            engine: Engine::Gaps {
+                phrases,
+                labels,
+                stop_words: Vec::new(),
+                case_insensitive,
+            },
+            reason: reason.to_string(),
+            replacement: None,
+            score: 0,
+            last_decision_score: 0,
+        }
+    }
+
+    /// Set stop words that reset every phrase's progress when encountered.
+    /// No-op (and documented as such) on a strict-mode rule.
+    pub fn with_stop_words<S: AsRef<str>>(mut self, words: Vec<S>) -> Self {
+        if let Engine::Gaps { stop_words, .. } = &mut self.engine {
+            *stop_words = words.iter().map(|s| s.as_ref().to_string()).collect();
+        }
+        self
+    }
+
+    /// Set the score contributed when this rule matches.
+    pub fn set_score(&mut self, score: u32) {
+        self.score = score;
+    }
+}
+
+impl Rule for ForbiddenSetRule {
+    fn feed(&mut self, chunk: &str) -> Decision {
+        if chunk.is_empty() {
+            return Decision::Allow;
+        }
+
+        match &mut self.engine {
+            Engine::Strict(ac) => {
+                let idxs = ac.feed(chunk);
+                if idxs.is_empty() {
+                    self.last_decision_score = 0;
+                    return Decision::Allow;
+                }
+                self.last_decision_score = self.score;
+                let labels: Vec<&str> = idxs.iter().map(|&i| ac.phrases[i].as_str()).collect();
+                if let Some(replacement) = &self.replacement {
+                    let mut rewritten = chunk.to_string();
+                    for label in &labels {
+                        rewritten = rewritten.replace(label, replacement.as_str());
+                    }
+                    Decision::Rewrite {
+                        replacement: rewritten,
+                    }
+                } else {
+                    Decision::Block {
+                        reason: format!("{} (matched: {})", self.reason, labels.join(", ")),
+                    }
+                }
+            }
+            Engine::Gaps {
+                phrases,
+                labels,
+                stop_words,
+                case_insensitive,
+            } => {
+                let mut matched = Vec::new();
+                for (i, p) in phrases.iter_mut().enumerate() {
+                    if p.advance(chunk, *case_insensitive, stop_words) {
+                        matched.push(labels[i].as_str());
+                        p.reset();
+                    }
+                }
+                if matched.is_empty() {
+                    self.last_decision_score = 0;
+                    return Decision::Allow;
+                }
+                self.last_decision_score = self.score;
+                Decision::Block {
+                    reason: format!("{} (matched: {})", self.reason, matched.join(", ")),
+                }
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.last_decision_score = 0;
+        match &mut self.engine {
+            Engine::Strict(ac) => ac.reset(),
+            Engine::Gaps { phrases, .. } => {
+                for p in phrases.iter_mut() {
+                    p.reset();
+                }
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        "forbidden_set"
+    }
+
+    fn last_score(&self) -> u32 {
+        self.last_decision_score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strict_single_phrase_one_chunk() {
+        let mut rule = ForbiddenSetRule::strict(vec!["hack"], "weapon instructions");
+        assert!(rule.feed("how to hack things").is_block());
+    }
+
+    #[test]
+    fn test_strict_multiple_phrases_one_pass() {
+        let mut rule = ForbiddenSetRule::strict(vec!["cat", "dog", "bird"], "pets");
+        let decision = rule.feed("a cat and a dog");
+        match decision {
+            Decision::Block { reason } => {
+                assert!(reason.contains("cat"));
+                assert!(reason.contains("dog"));
+                assert!(!reason.contains("bird"));
+            }
+            _ => panic!("expected block"),
+        }
+    }
+
+    #[test]
+    fn test_strict_no_match_allows() {
+        let mut rule = ForbiddenSetRule::strict(vec!["cat", "dog"], "pets");
+        assert!(rule.feed("a fish swims").is_allow());
+    }
+
+    #[test]
+    fn test_strict_match_split_across_chunks() {
+        let mut rule = ForbiddenSetRule::strict(vec!["hack"], "weapon instructions");
+        assert!(rule.feed("how to ha").is_allow());
+        assert!(rule.feed("ck things").is_block());
+    }
+
+    #[test]
+    fn test_strict_failure_link_overlapping_suffix() {
+        // "she" and "he" share a suffix; "he" must still fire via the
+        // failure link even though it's not on the direct goto path for "she".
+        let mut rule = ForbiddenSetRule::strict(vec!["he", "she", "his"], "pronouns");
+        let decision = rule.feed("she said");
+        match decision {
+            Decision::Block { reason } => {
+                assert!(reason.contains("he"));
+                assert!(reason.contains("she"));
+            }
+            _ => panic!("expected block"),
+        }
+    }
+
+    #[test]
+    fn test_strict_case_insensitive() {
+        let mut rule = ForbiddenSetRule::strict_case_insensitive(vec!["secret"], "leak");
+        assert!(rule.feed("SECRET data").is_block());
+    }
+
+    #[test]
+    fn test_strict_case_sensitive_default() {
+        let mut rule = ForbiddenSetRule::strict(vec!["secret"], "leak");
+        assert!(rule.feed("SECRET data").is_allow());
+    }
+
+    #[test]
+    fn test_strict_rewrite_replaces_matched_phrase() {
+        let mut rule = ForbiddenSetRule::strict_rewrite(vec!["secret", "password"], "[redacted]");
+        let decision = rule.feed("my secret is safe");
+        assert_eq!(
+            decision.rewritten_text(),
+            Some("my [redacted] is safe")
+        );
+    }
+
+    #[test]
+    fn test_strict_reset_clears_state() {
+        let mut rule = ForbiddenSetRule::strict(vec!["hack"], "weapon instructions");
+        rule.feed("how to ha");
+        rule.reset();
+        assert!(rule.feed("ck things").is_allow());
+    }
+
+    #[test]
+    fn test_strict_empty_chunk_allows() {
+        let mut rule = ForbiddenSetRule::strict(vec!["bad"], "test");
+        assert!(rule.feed("").is_allow());
+    }
+
+    #[test]
+    fn test_gaps_single_phrase_with_gap() {
+        let mut rule = ForbiddenSetRule::with_gaps(vec![vec!["how", "to", "hack"]], "threat");
+        assert!(rule.feed("how to safely hack").is_block());
+    }
+
+    #[test]
+    fn test_gaps_independent_phrase_progress() {
+        let mut rule = ForbiddenSetRule::with_gaps(
+            vec![vec!["how", "to", "hack"], vec!["steal", "password"]],
+            "threat",
+        );
+        assert!(rule.feed("how to ").is_allow());
+        assert!(rule.feed("steal a ").is_allow());
+        assert!(rule.feed("password").is_block());
+    }
+
+    #[test]
+    fn test_gaps_stop_word_resets_phrase() {
+        let mut rule = ForbiddenSetRule::with_gaps(vec![vec!["how", "to", "hack"]], "threat")
+            .with_stop_words(vec!["not"]);
+        assert!(rule.feed("how to not hack").is_allow());
+    }
+
+    #[test]
+    fn test_gaps_case_insensitive() {
+        let mut rule =
+            ForbiddenSetRule::with_gaps_case_insensitive(vec![vec!["secret", "password"]], "leak");
+        assert!(rule.feed("SECRET ").is_allow());
+        assert!(rule.feed("PASSWORD").is_block());
+    }
+
+    #[test]
+    fn test_gaps_reset_clears_all_phrases() {
+        let mut rule = ForbiddenSetRule::with_gaps(vec![vec!["how", "to", "hack"]], "threat");
+        rule.feed("how to");
+        rule.reset();
+        assert!(rule.feed("hack").is_allow());
+    }
+}
@@ -0,0 +1,348 @@
+//! Bayesian token-scoring rule trained on spam/ham corpora
+//!
+//! Unlike [`ForbiddenSequenceRule`](crate::rules::ForbiddenSequenceRule) and
+//! [`PatternRule`](crate::rules::PatternRule), which hard-match literal tokens
+//! or patterns, [`BayesRule`] never blocks or rewrites on its own. It only
+//! contributes a 0-100 score to the engine's accumulator (the same path
+//! [`ForbiddenSequenceRule::new_with_score`](crate::rules::ForbiddenSequenceRule::new_with_score)
+//! feeds), so a host composes it with `GuardEngine::with_score_threshold` to
+//! flag statistically "bad" text rather than only literal matches.
+//!
+//! This is Paul Graham's "A Plan for Spam" classifier: per-token spam/ham
+//! frequencies, combined via `P = Πp / (Πp + Π(1−p))` over the tokens whose
+//! probability is furthest from neutral.
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::core::{Decision, Rule};
+
+/// Probability assigned to a token never seen in training.
+const UNKNOWN_TOKEN_PROBABILITY: f64 = 0.4;
+/// Clamp bounds so no single token can drive `P` all the way to 0 or 1.
+const MIN_TOKEN_PROBABILITY: f64 = 0.01;
+const MAX_TOKEN_PROBABILITY: f64 = 0.99;
+/// Number of most-opinionated tokens combined into the final probability.
+const MAX_INTERESTING_TOKENS: usize = 15;
+/// Cap on the streaming token window, in characters, so memory stays bounded.
+const WINDOW_CHAR_CAP: usize = 4096;
+
+/// A rule that scores text by how "spammy" its tokens are, trained from
+/// example spam/ham text.
+///
+/// # Example
+///
+/// ```rust
+/// use streamguard::rules::BayesRule;
+/// use streamguard::Rule;
+///
+/// let mut rule = BayesRule::new();
+/// rule.train_spam("buy cheap viagra now");
+/// rule.train_ham("let's meet for lunch tomorrow");
+///
+/// rule.feed("buy viagra now");
+/// assert!(rule.last_score() > 50);
+/// ```
+pub struct BayesRule {
+    /// Per-token `(spam_count, ham_count)` observed during training.
+    token_counts: BTreeMap<String, (u32, u32)>,
+    /// Total spam messages trained, used to normalize per-token spam counts.
+    total_spam: u32,
+    /// Total ham messages trained, used to normalize per-token ham counts.
+    total_ham: u32,
+    /// Streaming token window, carried across chunks and capped in length.
+    window: String,
+    /// Score from the most recent `feed` call.
+    last_decision_score: u32,
+}
+
+/// A problem encountered while parsing a persisted model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BayesModelError {
+    /// The header line (`total_spam total_ham`) was missing or malformed.
+    BadHeader,
+    /// A token row did not have the `token\tspam\tham` shape.
+    BadRow(String),
+}
+
+impl BayesRule {
+    /// Create an untrained rule. Every token scores the neutral
+    /// [`UNKNOWN_TOKEN_PROBABILITY`] until [`Self::train_spam`] or
+    /// [`Self::train_ham`] is called.
+    pub fn new() -> Self {
+        Self {
+            token_counts: BTreeMap::new(),
+            total_spam: 0,
+            total_ham: 0,
+            window: String::new(),
+            last_decision_score: 0,
+        }
+    }
+
+    /// Tokenize `text` into lowercase alphanumeric runs.
+    fn tokenize(text: &str) -> Vec<String> {
+        text.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    /// Train on a message known to be spam, incrementing each token's spam
+    /// count once per occurrence and the spam total once.
+    pub fn train_spam(&mut self, text: &str) {
+        self.total_spam += 1;
+        for token in Self::tokenize(text) {
+            let entry = self.token_counts.entry(token).or_insert((0, 0));
+            entry.0 += 1;
+        }
+    }
+
+    /// Train on a message known to be ham (legitimate), incrementing each
+    /// token's ham count once per occurrence and the ham total once.
+    pub fn train_ham(&mut self, text: &str) {
+        self.total_ham += 1;
+        for token in Self::tokenize(text) {
+            let entry = self.token_counts.entry(token).or_insert((0, 0));
+            entry.1 += 1;
+        }
+    }
+
+    /// Probability that a single token indicates spam, clamped and defaulted
+    /// per [`UNKNOWN_TOKEN_PROBABILITY`] for an untrained corpus or token.
+    fn token_probability(&self, token: &str) -> f64 {
+        if self.total_spam == 0 && self.total_ham == 0 {
+            return UNKNOWN_TOKEN_PROBABILITY;
+        }
+        let Some((spam, ham)) = self.token_counts.get(token) else {
+            return UNKNOWN_TOKEN_PROBABILITY;
+        };
+
+        let spam_rate = if self.total_spam > 0 {
+            *spam as f64 / self.total_spam as f64
+        } else {
+            0.0
+        };
+        let ham_rate = if self.total_ham > 0 {
+            *ham as f64 / self.total_ham as f64
+        } else {
+            0.0
+        };
+
+        if spam_rate + ham_rate == 0.0 {
+            return UNKNOWN_TOKEN_PROBABILITY;
+        }
+
+        let p = spam_rate / (spam_rate + ham_rate);
+        p.clamp(MIN_TOKEN_PROBABILITY, MAX_TOKEN_PROBABILITY)
+    }
+
+    /// Combine the most opinionated tokens in `text` via Graham's formula,
+    /// returning `P` in `[0.0, 1.0]`. An untrained rule returns the neutral
+    /// [`UNKNOWN_TOKEN_PROBABILITY`].
+    fn classify(&self, text: &str) -> f64 {
+        if self.total_spam == 0 && self.total_ham == 0 {
+            return UNKNOWN_TOKEN_PROBABILITY;
+        }
+        let mut probabilities: Vec<f64> = Self::tokenize(text)
+            .iter()
+            .map(|t| self.token_probability(t))
+            .collect();
+
+        probabilities.sort_by(|a, b| {
+            let da = (a - 0.5).abs();
+            let db = (b - 0.5).abs();
+            db.partial_cmp(&da).unwrap_or(core::cmp::Ordering::Equal)
+        });
+        probabilities.truncate(MAX_INTERESTING_TOKENS);
+
+        if probabilities.is_empty() {
+            return UNKNOWN_TOKEN_PROBABILITY;
+        }
+
+        let product_p: f64 = probabilities.iter().product();
+        let product_not_p: f64 = probabilities.iter().map(|p| 1.0 - p).product();
+
+        if product_p + product_not_p == 0.0 {
+            return UNKNOWN_TOKEN_PROBABILITY;
+        }
+        product_p / (product_p + product_not_p)
+    }
+
+    /// Serialize the trained token table to a restorable string.
+    ///
+    /// Format is a header line of `total_spam total_ham` followed by one
+    /// `token\tspam\tham` row per trained token.
+    pub fn export_model(&self) -> String {
+        let mut out = format!("{} {}\n", self.total_spam, self.total_ham);
+        for (token, (spam, ham)) in &self.token_counts {
+            out.push_str(&format!("{}\t{}\t{}\n", token, spam, ham));
+        }
+        out
+    }
+
+    /// Load a token table previously produced by [`Self::export_model`],
+    /// replacing any existing training.
+    pub fn import_model(&mut self, data: &str) -> Result<(), BayesModelError> {
+        let mut lines = data.lines();
+        let header = lines.next().ok_or(BayesModelError::BadHeader)?;
+        let mut parts = header.split_whitespace();
+        let total_spam: u32 = parts
+            .next()
+            .and_then(|s| s.parse().ok())
+            .ok_or(BayesModelError::BadHeader)?;
+        let total_ham: u32 = parts
+            .next()
+            .and_then(|s| s.parse().ok())
+            .ok_or(BayesModelError::BadHeader)?;
+
+        let mut token_counts = BTreeMap::new();
+        for line in lines {
+            if line.is_empty() {
+                continue;
+            }
+            let mut fields = line.split('\t');
+            let token = fields.next().ok_or_else(|| BayesModelError::BadRow(line.to_string()))?;
+            let spam: u32 = fields
+                .next()
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| BayesModelError::BadRow(line.to_string()))?;
+            let ham: u32 = fields
+                .next()
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| BayesModelError::BadRow(line.to_string()))?;
+            token_counts.insert(token.to_string(), (spam, ham));
+        }
+
+        self.total_spam = total_spam;
+        self.total_ham = total_ham;
+        self.token_counts = token_counts;
+        Ok(())
+    }
+}
+
+impl Default for BayesRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Keep the last `n` characters of `text`, respecting UTF-8 boundaries.
+fn keep_last_chars(text: &str, n: usize) -> String {
+    match text.char_indices().rev().nth(n.saturating_sub(1)) {
+        Some((idx, _)) => text[idx..].to_string(),
+        None => text.to_string(),
+    }
+}
+
+impl Rule for BayesRule {
+    fn feed(&mut self, chunk: &str) -> Decision {
+        if chunk.is_empty() {
+            self.last_decision_score = 0;
+            return Decision::Allow;
+        }
+
+        self.window.push_str(chunk);
+        if self.window.chars().count() > WINDOW_CHAR_CAP {
+            self.window = keep_last_chars(&self.window, WINDOW_CHAR_CAP);
+        }
+
+        let probability = self.classify(&self.window);
+        self.last_decision_score = (probability * 100.0).round() as u32;
+
+        // Scoring-only rule: never blocks or rewrites itself.
+        Decision::Allow
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+        self.last_decision_score = 0;
+    }
+
+    fn name(&self) -> &str {
+        "bayes"
+    }
+
+    fn last_score(&self) -> u32 {
+        self.last_decision_score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_untrained_rule_is_neutral() {
+        let mut rule = BayesRule::new();
+        rule.feed("anything at all");
+        assert_eq!(rule.last_score(), (UNKNOWN_TOKEN_PROBABILITY * 100.0).round() as u32);
+    }
+
+    #[test]
+    fn test_spam_scores_higher_than_ham() {
+        let mut spam_rule = BayesRule::new();
+        spam_rule.train_spam("buy cheap viagra now");
+        spam_rule.train_spam("viagra discount pills now");
+        spam_rule.train_ham("let's meet for lunch tomorrow");
+        spam_rule.train_ham("meeting notes attached for review");
+
+        spam_rule.feed("buy viagra now");
+        let spam_score = spam_rule.last_score();
+
+        let mut ham_rule = BayesRule::new();
+        ham_rule.train_spam("buy cheap viagra now");
+        ham_rule.train_spam("viagra discount pills now");
+        ham_rule.train_ham("let's meet for lunch tomorrow");
+        ham_rule.train_ham("meeting notes attached for review");
+        ham_rule.feed("let's meet tomorrow");
+        let ham_score = ham_rule.last_score();
+
+        assert!(spam_score > ham_score);
+    }
+
+    #[test]
+    fn test_window_survives_chunk_boundaries() {
+        let mut rule = BayesRule::new();
+        rule.train_spam("buy cheap viagra now");
+        rule.train_ham("let's meet for lunch tomorrow");
+
+        rule.feed("buy ");
+        rule.feed("viagra ");
+        rule.feed("now");
+        assert!(rule.last_score() > 50);
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let mut rule = BayesRule::new();
+        rule.train_spam("buy viagra now");
+        rule.train_ham("lunch meeting notes");
+
+        let mut restored = BayesRule::new();
+        restored.import_model(&rule.export_model()).unwrap();
+
+        rule.feed("buy viagra now");
+        restored.feed("buy viagra now");
+        assert_eq!(rule.last_score(), restored.last_score());
+    }
+
+    #[test]
+    fn test_import_rejects_malformed_header() {
+        let mut rule = BayesRule::new();
+        assert_eq!(rule.import_model("not a header"), Err(BayesModelError::BadHeader));
+    }
+
+    #[test]
+    fn test_reset_clears_window_and_score() {
+        let mut rule = BayesRule::new();
+        rule.train_spam("buy viagra now");
+        rule.feed("buy viagra now");
+        assert!(rule.last_score() > 0);
+
+        rule.reset();
+        assert_eq!(rule.last_score(), 0);
+    }
+}
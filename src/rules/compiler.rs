@@ -0,0 +1,321 @@
+//! A small line-oriented DSL for authoring rules as text
+//!
+//! Rules normally have to be constructed programmatically and wired through
+//! [`GuardEngine::add_rule`](crate::GuardEngine::add_rule). This compiler lets
+//! a rule set be loaded from a config string instead, the way Sieve filters or
+//! CloudFormation Guard policies are authored as text and compiled.
+//!
+//! # Grammar
+//!
+//! Each non-empty, non-`#` line has the shape:
+//!
+//! ```text
+//! RULE_KIND ARGS => ACTION "reason-or-replacement" [score=N]
+//! ```
+//!
+//! Examples:
+//!
+//! ```text
+//! sequence ["how","to","build","bomb"] => block "weapon instructions" score=5
+//! pattern email => rewrite "[redacted]"
+//! pattern ipv4 => block "ip leak"
+//! ```
+//!
+//! `RULE_KIND` is `sequence` or `pattern`; for patterns `ARGS` is one of the
+//! preset names `email`, `email_strict`, `url`, `ipv4`, `credit_card`. The
+//! action keyword is `block`, `rewrite`, or `allow`. Syntax errors surface as
+//! a [`CompileError`] carrying the line and column rather than panicking.
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::core::Rule;
+use crate::rules::{ForbiddenSequenceRule, PatternPreset, PatternRule};
+
+/// The specific problem encountered while compiling a rule line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompileErrorKind {
+    /// The `RULE_KIND` token was not `sequence` or `pattern`.
+    UnknownRuleKind(String),
+    /// The pattern name did not map to a known preset.
+    UnknownPattern(String),
+    /// The action keyword was not `block`, `rewrite`, or `allow`.
+    UnknownAction(String),
+    /// The `=>` action separator was missing.
+    ExpectedArrow,
+    /// A quoted string was opened but never closed.
+    UnterminatedString,
+    /// The reason/replacement string was missing for the action.
+    MissingReason,
+    /// A sequence's bracketed token list was malformed.
+    BadTokenList,
+    /// The `score=N` suffix was not a valid integer.
+    BadScore(String),
+}
+
+/// An error produced while compiling the rule DSL, anchored to a location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompileError {
+    /// 1-based line number where the error occurred.
+    pub line: usize,
+    /// 1-based column within the line.
+    pub column: usize,
+    /// What went wrong.
+    pub kind: CompileErrorKind,
+}
+
+impl CompileError {
+    fn new(line: usize, column: usize, kind: CompileErrorKind) -> Self {
+        Self { line, column, kind }
+    }
+}
+
+/// Which action a compiled line performs on a match.
+enum Action {
+    Block,
+    Rewrite,
+    Allow,
+}
+
+/// Compile the DSL source into a list of boxed rules.
+///
+/// Blank lines and lines whose first non-whitespace character is `#` are
+/// ignored.
+pub fn compile(source: &str) -> Result<Vec<Box<dyn Rule>>, CompileError> {
+    let mut rules: Vec<Box<dyn Rule>> = Vec::new();
+
+    for (idx, raw) in source.lines().enumerate() {
+        let line_no = idx + 1;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        rules.push(compile_line(raw, line_no)?);
+    }
+
+    Ok(rules)
+}
+
+/// Compile a single non-empty rule line.
+fn compile_line(line: &str, line_no: usize) -> Result<Box<dyn Rule>, CompileError> {
+    // Split on the `=>` action separator.
+    let arrow = line
+        .find("=>")
+        .ok_or_else(|| CompileError::new(line_no, line.len() + 1, CompileErrorKind::ExpectedArrow))?;
+    let (lhs, rhs) = line.split_at(arrow);
+    let rhs = &rhs[2..]; // drop the "=>"
+
+    let lhs = lhs.trim();
+    let mut lhs_parts = lhs.splitn(2, char::is_whitespace);
+    let kind = lhs_parts.next().unwrap_or("").trim();
+    let args = lhs_parts.next().unwrap_or("").trim();
+
+    // Parse the action and its quoted string from the right-hand side.
+    let (action, payload, score) = parse_rhs(rhs, line_no)?;
+
+    match kind {
+        "sequence" => {
+            let tokens = parse_token_list(args).ok_or_else(|| {
+                CompileError::new(line_no, col_of(line, args), CompileErrorKind::BadTokenList)
+            })?;
+            build_sequence(tokens, action, payload, score)
+        }
+        "pattern" => {
+            let preset = parse_preset(args).ok_or_else(|| {
+                CompileError::new(
+                    line_no,
+                    col_of(line, args),
+                    CompileErrorKind::UnknownPattern(args.to_string()),
+                )
+            })?;
+            build_pattern(preset, action, payload)
+        }
+        other => Err(CompileError::new(
+            line_no,
+            col_of(line, kind),
+            CompileErrorKind::UnknownRuleKind(other.to_string()),
+        )),
+    }
+}
+
+/// Parse `ACTION "payload" [score=N]` from the right-hand side of a line.
+fn parse_rhs(rhs: &str, line_no: usize) -> Result<(Action, String, Option<u32>), CompileError> {
+    let rhs = rhs.trim_start();
+    let mut parts = rhs.splitn(2, char::is_whitespace);
+    let action_word = parts.next().unwrap_or("").trim();
+    let action = match action_word {
+        "block" => Action::Block,
+        "rewrite" => Action::Rewrite,
+        "allow" => Action::Allow,
+        other => {
+            return Err(CompileError::new(
+                line_no,
+                1,
+                CompileErrorKind::UnknownAction(other.to_string()),
+            ))
+        }
+    };
+
+    let rest = parts.next().unwrap_or("").trim();
+    if matches!(action, Action::Allow) && rest.is_empty() {
+        return Ok((action, String::new(), None));
+    }
+
+    // The remainder begins with a quoted string, optionally followed by score=N.
+    let (payload, after) = parse_quoted(rest)
+        .ok_or_else(|| CompileError::new(line_no, 1, CompileErrorKind::MissingReason))?;
+
+    let score = match after.trim() {
+        "" => None,
+        suffix => {
+            let value = suffix
+                .strip_prefix("score=")
+                .ok_or_else(|| {
+                    CompileError::new(line_no, 1, CompileErrorKind::BadScore(suffix.to_string()))
+                })?;
+            Some(
+                value
+                    .trim()
+                    .parse::<u32>()
+                    .map_err(|_| CompileError::new(line_no, 1, CompileErrorKind::BadScore(value.to_string())))?,
+            )
+        }
+    };
+
+    Ok((action, payload, score))
+}
+
+/// Parse a leading `"..."` quoted string, returning (contents, remainder).
+fn parse_quoted(input: &str) -> Option<(String, &str)> {
+    let input = input.trim_start();
+    let mut chars = input.char_indices();
+    if chars.next()?.1 != '"' {
+        return None;
+    }
+    let mut out = String::new();
+    let mut escaped = false;
+    for (i, c) in chars {
+        if escaped {
+            out.push(c);
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            return Some((out, &input[i + 1..]));
+        } else {
+            out.push(c);
+        }
+    }
+    None
+}
+
+/// Parse a bracketed list of quoted strings: `["a","b"]`.
+fn parse_token_list(input: &str) -> Option<Vec<String>> {
+    let inner = input.trim().strip_prefix('[')?.strip_suffix(']')?;
+    let mut tokens = Vec::new();
+    let mut rest = inner.trim();
+    while !rest.is_empty() {
+        let (token, after) = parse_quoted(rest)?;
+        tokens.push(token);
+        rest = after.trim_start().trim_start_matches(',').trim_start();
+    }
+    Some(tokens)
+}
+
+/// Map a preset name to its [`PatternPreset`].
+fn parse_preset(name: &str) -> Option<PatternPreset> {
+    match name {
+        "email" => Some(PatternPreset::Email),
+        "email_strict" => Some(PatternPreset::EmailStrict),
+        "url" => Some(PatternPreset::Url),
+        "ipv4" => Some(PatternPreset::Ipv4),
+        "credit_card" => Some(PatternPreset::CreditCard),
+        _ => None,
+    }
+}
+
+/// Build a forbidden-sequence rule from the parsed line parts.
+fn build_sequence(
+    tokens: Vec<String>,
+    action: Action,
+    payload: String,
+    score: Option<u32>,
+) -> Result<Box<dyn Rule>, CompileError> {
+    match action {
+        Action::Rewrite => Ok(Box::new(ForbiddenSequenceRule::new_with_rewrite(
+            tokens, &payload,
+        ))),
+        _ => {
+            let mut rule = ForbiddenSequenceRule::with_gaps(tokens, &payload);
+            if let Some(score) = score {
+                rule.set_score(score);
+            }
+            Ok(Box::new(rule))
+        }
+    }
+}
+
+/// Build a preset pattern rule from the parsed line parts.
+fn build_pattern(
+    preset: PatternPreset,
+    action: Action,
+    payload: String,
+) -> Result<Box<dyn Rule>, CompileError> {
+    match action {
+        Action::Rewrite => {
+            let rule = match preset {
+                PatternPreset::Url => PatternRule::url_rewrite(&payload),
+                PatternPreset::Ipv4 => PatternRule::ipv4_rewrite(&payload),
+                PatternPreset::CreditCard | PatternPreset::CreditCardStrict => {
+                    PatternRule::credit_card_rewrite(&payload)
+                }
+                _ => PatternRule::email_rewrite(&payload),
+            };
+            Ok(Box::new(rule))
+        }
+        _ => Ok(Box::new(PatternRule::from_preset(preset, &payload))),
+    }
+}
+
+/// Best-effort 1-based column of `needle` within `line`.
+fn col_of(line: &str, needle: &str) -> usize {
+    line.find(needle).map(|p| p + 1).unwrap_or(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compile_sequence_block() {
+        let rules = compile(r#"sequence ["how","to","hack"] => block "security" score=5"#).unwrap();
+        assert_eq!(rules.len(), 1);
+    }
+
+    #[test]
+    fn test_compile_pattern_rewrite() {
+        let rules = compile(r#"pattern email => rewrite "[redacted]""#).unwrap();
+        assert_eq!(rules.len(), 1);
+    }
+
+    #[test]
+    fn test_comments_and_blanks_skipped() {
+        let src = "# a comment\n\npattern ipv4 => block \"ip leak\"\n";
+        let rules = compile(src).unwrap();
+        assert_eq!(rules.len(), 1);
+    }
+
+    #[test]
+    fn test_unknown_rule_kind_errors() {
+        let err = compile("widget foo => block \"x\"").unwrap_err();
+        assert_eq!(err.line, 1);
+        assert!(matches!(err.kind, CompileErrorKind::UnknownRuleKind(_)));
+    }
+
+    #[test]
+    fn test_missing_arrow_errors() {
+        let err = compile("pattern email block \"x\"").unwrap_err();
+        assert_eq!(err.kind, CompileErrorKind::ExpectedArrow);
+    }
+}
@@ -0,0 +1,272 @@
+//! Programmatic boolean composition of arbitrary rules (AND/OR/NOT)
+//!
+//! [`crate::rules::expr`] already lets a policy combine signals with a
+//! text DSL, but only over its fixed set of built-in leaf terms (the
+//! handful of presets it knows how to name). `CompositeRule` instead
+//! combines any boxed [`Rule`] trait object -- a custom [`PatternRule`],
+//! a [`crate::rules::RegexRule`], a Python callback rule, or another
+//! `CompositeRule` -- so a policy like "block only if an email address AND
+//! a credential-leak sequence both appear in the stream" can be built from
+//! whatever rules a caller already has, programmatically, with
+//! [`CompositeRule::all_of`], [`CompositeRule::any_of`], and
+//! [`CompositeRule::not`].
+//!
+//! # Latching, not same-chunk matching
+//!
+//! [`crate::rules::expr`]'s `And`/`Or` nodes only count a child as matching
+//! if its own streaming buffer *currently* holds a match -- `email AND url`
+//! requires both to appear within the same buffered state, not ever across
+//! the rule's lifetime. `CompositeRule` instead *latches*: once a leaf rule
+//! produces a non-`Allow` decision, it is considered matched for the rest of
+//! the stream, even after its own internal state (e.g. a [`PatternRule`]'s
+//! buffer) has moved on. This is what makes "block unless a disclaimer is
+//! also present somewhere in the stream" expressible as
+//! `all_of([forbidden_sequence, not(disclaimer_pattern)])` rather than
+//! requiring both to land in the same chunk. Resetting the composite
+//! ([`Rule::reset`]) clears every leaf's latch along with its own state.
+//!
+//! [`PatternRule`]: crate::rules::PatternRule
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::core::{Decision, Rule};
+
+/// A node in the composite's boolean tree.
+enum Node {
+    /// A wrapped rule plus whether it has latched a match since the last
+    /// [`Node::reset`].
+    Leaf { rule: Box<dyn Rule>, latched: bool },
+    Not(Box<Node>),
+    AllOf(Vec<Node>),
+    AnyOf(Vec<Node>),
+}
+
+impl Node {
+    fn leaf(rule: Box<dyn Rule>) -> Self {
+        Node::Leaf {
+            rule,
+            latched: false,
+        }
+    }
+
+    /// Feed `chunk` to this node, returning whether it matches (latched, for
+    /// leaves) and the score to contribute to the composite's total.
+    fn feed(&mut self, chunk: &str) -> (bool, u32) {
+        match self {
+            Node::Leaf { rule, latched } => {
+                let decision = rule.feed(chunk);
+                if !decision.is_allow() {
+                    *latched = true;
+                }
+                (*latched, rule.last_score())
+            }
+            Node::Not(inner) => {
+                let (matched, score) = inner.feed(chunk);
+                (!matched, score)
+            }
+            Node::AllOf(children) => {
+                let mut matched = true;
+                let mut score = 0u32;
+                for child in children {
+                    let (m, s) = child.feed(chunk);
+                    matched &= m;
+                    score += s;
+                }
+                (matched, score)
+            }
+            Node::AnyOf(children) => {
+                let mut matched = false;
+                let mut score = 0u32;
+                for child in children {
+                    let (m, s) = child.feed(chunk);
+                    matched |= m;
+                    score = score.max(s);
+                }
+                (matched, score)
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        match self {
+            Node::Leaf { rule, latched } => {
+                rule.reset();
+                *latched = false;
+            }
+            Node::Not(inner) => inner.reset(),
+            Node::AllOf(children) | Node::AnyOf(children) => {
+                for child in children {
+                    child.reset();
+                }
+            }
+        }
+    }
+}
+
+/// A rule built by combining other rules with `AllOf`/`AnyOf`/`Not`.
+///
+/// Build one with [`Self::all_of`], [`Self::any_of`], or [`Self::not`], and
+/// nest them by boxing a `CompositeRule` as one of another's children -- it
+/// implements [`Rule`] like any other leaf.
+///
+/// ```rust
+/// use streamguard::Rule;
+/// use streamguard::rules::{CompositeRule, ForbiddenSequenceRule, PatternRule};
+///
+/// // Block only if an email address AND a credential-leak sequence both
+/// // appear somewhere in the stream.
+/// let mut rule = CompositeRule::all_of(
+///     vec![
+///         Box::new(PatternRule::email("email present")),
+///         Box::new(ForbiddenSequenceRule::with_gaps(
+///             vec!["password", "is"],
+///             "credential leak",
+///         )),
+///     ],
+///     "email and credential leak both present",
+/// );
+///
+/// assert!(rule.feed("contact me at user@example.com").is_allow());
+/// assert!(rule.feed("the password is hunter2").is_block());
+/// ```
+pub struct CompositeRule {
+    root: Node,
+    reason: String,
+    last_decision_score: u32,
+}
+
+impl CompositeRule {
+    /// Block once every rule in `rules` has latched a match.
+    pub fn all_of(rules: Vec<Box<dyn Rule>>, reason: &str) -> Self {
+        Self::from_node(Node::AllOf(rules.into_iter().map(Node::leaf).collect()), reason)
+    }
+
+    /// Block once any rule in `rules` has latched a match.
+    pub fn any_of(rules: Vec<Box<dyn Rule>>, reason: &str) -> Self {
+        Self::from_node(Node::AnyOf(rules.into_iter().map(Node::leaf).collect()), reason)
+    }
+
+    /// Block while `rule` has *not* latched a match -- useful combined with
+    /// [`Self::all_of`] to express "block X unless Y is also present".
+    pub fn not(rule: Box<dyn Rule>, reason: &str) -> Self {
+        Self::from_node(Node::Not(Box::new(Node::leaf(rule))), reason)
+    }
+
+    fn from_node(root: Node, reason: &str) -> Self {
+        Self {
+            root,
+            reason: reason.to_string(),
+            last_decision_score: 0,
+        }
+    }
+}
+
+impl Rule for CompositeRule {
+    fn feed(&mut self, chunk: &str) -> Decision {
+        let (matched, score) = self.root.feed(chunk);
+        self.last_decision_score = score;
+        if matched {
+            Decision::Block {
+                reason: self.reason.clone(),
+            }
+        } else {
+            Decision::Allow
+        }
+    }
+
+    fn reset(&mut self) {
+        self.root.reset();
+    }
+
+    fn name(&self) -> &str {
+        "composite_rule"
+    }
+
+    fn last_score(&self) -> u32 {
+        self.last_decision_score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{ForbiddenSequenceRule, PatternRule};
+
+    fn email_rule() -> Box<dyn Rule> {
+        Box::new(PatternRule::email("email"))
+    }
+
+    fn leak_rule() -> Box<dyn Rule> {
+        Box::new(ForbiddenSequenceRule::with_gaps(
+            vec!["password", "is"],
+            "leak",
+        ))
+    }
+
+    #[test]
+    fn test_all_of_requires_every_rule_to_latch() {
+        let mut rule = CompositeRule::all_of(vec![email_rule(), leak_rule()], "both present");
+        assert!(rule.feed("just an email user@example.com").is_allow());
+        // The email rule already latched on the previous chunk, so only the
+        // leak sequence needs to appear now for the composite to block.
+        assert!(rule.feed("the password is hunter2").is_block());
+    }
+
+    #[test]
+    fn test_all_of_latches_across_chunks_unlike_same_chunk_and() {
+        let mut rule = CompositeRule::all_of(vec![email_rule(), leak_rule()], "both present");
+        assert!(rule.feed("user@example.com").is_allow());
+        assert!(rule.feed("nothing interesting here").is_allow());
+        // Email latched two chunks ago; the leak rule latches now.
+        assert!(rule.feed("the password is hunter2").is_block());
+    }
+
+    #[test]
+    fn test_any_of_matches_either_rule() {
+        let mut rule = CompositeRule::any_of(vec![email_rule(), leak_rule()], "either present");
+        assert!(rule.feed("contact: user@example.com").is_block());
+    }
+
+    #[test]
+    fn test_not_inverts_match() {
+        let mut rule = CompositeRule::not(email_rule(), "no email present");
+        assert!(rule.feed("no match here").is_block());
+        rule.reset();
+        assert!(rule.feed("user@example.com").is_allow());
+    }
+
+    #[test]
+    fn test_all_of_with_not_expresses_unless_disclaimer() {
+        // "block the leak sequence unless a disclaimer email is also present"
+        let mut rule = CompositeRule::all_of(
+            vec![leak_rule(), Box::new(CompositeRule::not(email_rule(), "no disclaimer"))],
+            "leak without disclaimer",
+        );
+        assert!(rule.feed("the password is hunter2").is_block());
+
+        rule.reset();
+        assert!(rule.feed("the password is hunter2, contact user@example.com").is_allow());
+    }
+
+    #[test]
+    fn test_reset_clears_latch_state() {
+        let mut rule = CompositeRule::all_of(vec![email_rule(), leak_rule()], "both present");
+        assert!(rule.feed("user@example.com").is_allow());
+        rule.reset();
+        // The email latch from before reset must not carry over.
+        assert!(rule.feed("the password is hunter2").is_allow());
+    }
+
+    #[test]
+    fn test_nested_composite_rule_as_leaf() {
+        let inner = CompositeRule::any_of(vec![email_rule(), leak_rule()], "either present");
+        let mut outer = CompositeRule::all_of(
+            vec![Box::new(inner), Box::new(PatternRule::ipv4("ip present"))],
+            "signal and ip",
+        );
+        assert!(outer.feed("user@example.com").is_allow());
+        assert!(outer.feed("from 10.0.0.1").is_block());
+    }
+}
@@ -0,0 +1,565 @@
+//! Boolean expression DSL for composing rules with AND/OR/NOT
+//!
+//! Rules normally fire independently and the engine just takes the first
+//! non-`Allow` decision (see [`GuardEngine::add_rule`](crate::GuardEngine::add_rule)).
+//! This module lets a single composite rule be built from a Sieve-style test
+//! expression over built-in leaf terms, so a policy can require several
+//! signals at once instead of only OR-ing standalone rules together. Its
+//! `And`/`Or` nodes only match within a single `feed` call; for a
+//! programmatic builder over arbitrary rules that latches a match across the
+//! whole stream, see [`crate::rules::composite`] instead.
+//!
+//! # Grammar
+//!
+//! ```text
+//! expr    := or_expr
+//! or_expr := and_expr ("OR" and_expr)*
+//! and_expr:= not_expr ("AND" not_expr)*
+//! not_expr:= "NOT" not_expr | primary
+//! primary := "(" expr ")" | leaf
+//! leaf    := PRESET | "seq" "(" STRING ("," STRING)* ")" | "score" "(" IDENT ")" ">" NUMBER
+//! ```
+//!
+//! `PRESET` is one of the built-in pattern names: `email`, `email_strict`,
+//! `url`, `ipv4`, `credit_card`. `seq(...)` is an inline forbidden sequence.
+//! `score(NAME) > N` thresholds a Bayesian score (see
+//! [`BayesRule`](crate::rules::BayesRule)); `NAME` is accepted for
+//! readability but, since the crate has no named registry of pluggable
+//! scoring sources yet, every `score(...)` leaf compiles to the same kind of
+//! (untrained) classifier today.
+//!
+//! Examples:
+//!
+//! ```text
+//! email AND NOT url
+//! seq("how","to","hack") OR score(malware) > 50
+//! (email OR url) AND NOT seq("unsubscribe")
+//! ```
+//!
+//! Operator precedence is `NOT` > `AND` > `OR`, with parens for grouping.
+//! Syntax errors surface as an [`ExprError`] carrying the byte offset of the
+//! offending token rather than panicking.
+//!
+//! A composite `And` only reports a match once every child's own buffered
+//! stream currently holds a match (see [`Node`] below), so `email AND url`
+//! requires both signals within the same feed rather than ever across the
+//! rule's lifetime.
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::core::{Decision, Rule};
+use crate::rules::{BayesRule, ForbiddenSequenceRule, PatternRule};
+
+/// What went wrong while compiling an expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExprErrorKind {
+    /// The input ended where a token was still expected.
+    UnexpectedEnd,
+    /// A token was present but didn't fit the grammar at this point.
+    UnexpectedToken(String),
+    /// A leaf identifier didn't match any known preset or leaf keyword.
+    UnknownLeaf(String),
+    /// A `seq(...)` argument list, or the name inside `score(...)`, was
+    /// malformed.
+    BadSeqArgs,
+    /// A `score(...) > N` comparison's `N` was not a valid integer.
+    BadScore(String),
+    /// A closing `)` was missing.
+    UnclosedParen,
+}
+
+/// An error produced while compiling an [`expr`] string, anchored to the
+/// byte offset of the offending token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExprError {
+    /// Byte offset into the source where the problem was found.
+    pub offset: usize,
+    /// What went wrong.
+    pub kind: ExprErrorKind,
+}
+
+impl ExprError {
+    fn new(offset: usize, kind: ExprErrorKind) -> Self {
+        Self { offset, kind }
+    }
+}
+
+/// A single lexical token, tagged with the byte offset it started at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    String(String),
+    Number(u32),
+    LParen,
+    RParen,
+    Comma,
+    Gt,
+    And,
+    Or,
+    Not,
+}
+
+/// Split `source` into a flat token stream, pairing each token with the byte
+/// offset it started at.
+///
+/// Walks `char_indices` rather than bytes so offsets stay valid UTF-8
+/// boundaries, consistent with how the rest of the crate handles text.
+fn lex(source: &str) -> Result<Vec<(usize, Token)>, ExprError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some(&(i, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        match c {
+            '(' => {
+                tokens.push((i, Token::LParen));
+                chars.next();
+            }
+            ')' => {
+                tokens.push((i, Token::RParen));
+                chars.next();
+            }
+            ',' => {
+                tokens.push((i, Token::Comma));
+                chars.next();
+            }
+            '>' => {
+                tokens.push((i, Token::Gt));
+                chars.next();
+            }
+            '"' => {
+                let start = i;
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        None => return Err(ExprError::new(start, ExprErrorKind::UnexpectedEnd)),
+                        Some((_, '"')) => break,
+                        Some((_, ch)) => value.push(ch),
+                    }
+                }
+                tokens.push((start, Token::String(value)));
+            }
+            _ if c.is_ascii_digit() => {
+                let start = i;
+                let mut end = i + c.len_utf8();
+                chars.next();
+                while let Some(&(j, ch)) = chars.peek() {
+                    if !ch.is_ascii_digit() {
+                        break;
+                    }
+                    end = j + ch.len_utf8();
+                    chars.next();
+                }
+                let text = &source[start..end];
+                let value = text
+                    .parse::<u32>()
+                    .map_err(|_| ExprError::new(start, ExprErrorKind::BadScore(text.to_string())))?;
+                tokens.push((start, Token::Number(value)));
+            }
+            _ if c.is_alphanumeric() || c == '_' => {
+                let start = i;
+                let mut end = i + c.len_utf8();
+                chars.next();
+                while let Some(&(j, ch)) = chars.peek() {
+                    if !(ch.is_alphanumeric() || ch == '_') {
+                        break;
+                    }
+                    end = j + ch.len_utf8();
+                    chars.next();
+                }
+                let word = &source[start..end];
+                let token = match word {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    _ => Token::Ident(word.to_string()),
+                };
+                tokens.push((start, token));
+            }
+            other => {
+                return Err(ExprError::new(
+                    i,
+                    ExprErrorKind::UnexpectedToken(other.to_string()),
+                ))
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// A node in the compiled boolean expression tree.
+///
+/// Every node's [`Node::feed`] is driven with the *same* chunk on every call,
+/// so an `And` only reports a match once all of its children's own streaming
+/// buffers currently hold a match -- this is what lets `email AND url`
+/// require both signals within the current buffered stream rather than ever
+/// (across the rule's whole lifetime).
+enum Node {
+    Leaf(Box<dyn Rule>),
+    Not(Box<Node>),
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+}
+
+impl Node {
+    /// Feed `chunk` to this node, returning whether it currently matches and
+    /// the score to contribute to the composite's total.
+    fn feed(&mut self, chunk: &str) -> (bool, u32) {
+        match self {
+            Node::Leaf(rule) => {
+                let decision = rule.feed(chunk);
+                (!decision.is_allow(), rule.last_score())
+            }
+            Node::Not(inner) => {
+                let (matched, score) = inner.feed(chunk);
+                (!matched, score)
+            }
+            Node::And(lhs, rhs) => {
+                let (lm, ls) = lhs.feed(chunk);
+                let (rm, rs) = rhs.feed(chunk);
+                (lm && rm, ls + rs)
+            }
+            Node::Or(lhs, rhs) => {
+                let (lm, ls) = lhs.feed(chunk);
+                let (rm, rs) = rhs.feed(chunk);
+                (lm || rm, ls.max(rs))
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        match self {
+            Node::Leaf(rule) => rule.reset(),
+            Node::Not(inner) => inner.reset(),
+            Node::And(lhs, rhs) | Node::Or(lhs, rhs) => {
+                lhs.reset();
+                rhs.reset();
+            }
+        }
+    }
+}
+
+/// A rule compiled from a boolean expression over built-in leaf terms.
+///
+/// Built via [`compile`] or [`GuardEngine::add_expression`](crate::GuardEngine::add_expression);
+/// not constructed directly.
+pub struct ExpressionRule {
+    root: Node,
+    source: String,
+    last_decision_score: u32,
+}
+
+impl Rule for ExpressionRule {
+    fn feed(&mut self, chunk: &str) -> Decision {
+        let (matched, score) = self.root.feed(chunk);
+        self.last_decision_score = score;
+        if matched {
+            Decision::Block {
+                reason: format!("expression matched: {}", self.source),
+            }
+        } else {
+            Decision::Allow
+        }
+    }
+
+    fn reset(&mut self) {
+        self.root.reset();
+    }
+
+    fn name(&self) -> &str {
+        "expression_rule"
+    }
+
+    fn last_score(&self) -> u32 {
+        self.last_decision_score
+    }
+}
+
+/// Compile a boolean expression string into an [`ExpressionRule`].
+pub fn compile(source: &str) -> Result<ExpressionRule, ExprError> {
+    let tokens = lex(source)?;
+    let mut parser = Parser {
+        tokens: &tokens,
+        pos: 0,
+        end_offset: source.len(),
+    };
+    let root = parser.parse_or()?;
+    if let Some((offset, token)) = parser.peek() {
+        return Err(ExprError::new(
+            offset,
+            ExprErrorKind::UnexpectedToken(format!("{:?}", token)),
+        ));
+    }
+
+    Ok(ExpressionRule {
+        root,
+        source: source.to_string(),
+        last_decision_score: 0,
+    })
+}
+
+struct Parser<'a> {
+    tokens: &'a [(usize, Token)],
+    pos: usize,
+    end_offset: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<(usize, &Token)> {
+        self.tokens.get(self.pos).map(|(o, t)| (*o, t))
+    }
+
+    fn next(&mut self) -> Option<(usize, &Token)> {
+        let item = self.peek();
+        if item.is_some() {
+            self.pos += 1;
+        }
+        item
+    }
+
+    fn current_offset(&self) -> usize {
+        self.peek().map(|(o, _)| o).unwrap_or(self.end_offset)
+    }
+
+    /// `or_expr := and_expr ("OR" and_expr)*`
+    fn parse_or(&mut self) -> Result<Node, ExprError> {
+        let mut node = self.parse_and()?;
+        while matches!(self.peek(), Some((_, Token::Or))) {
+            self.next();
+            let rhs = self.parse_and()?;
+            node = Node::Or(Box::new(node), Box::new(rhs));
+        }
+        Ok(node)
+    }
+
+    /// `and_expr := not_expr ("AND" not_expr)*`
+    fn parse_and(&mut self) -> Result<Node, ExprError> {
+        let mut node = self.parse_not()?;
+        while matches!(self.peek(), Some((_, Token::And))) {
+            self.next();
+            let rhs = self.parse_not()?;
+            node = Node::And(Box::new(node), Box::new(rhs));
+        }
+        Ok(node)
+    }
+
+    /// `not_expr := "NOT" not_expr | primary`
+    fn parse_not(&mut self) -> Result<Node, ExprError> {
+        if matches!(self.peek(), Some((_, Token::Not))) {
+            self.next();
+            let inner = self.parse_not()?;
+            return Ok(Node::Not(Box::new(inner)));
+        }
+        self.parse_primary()
+    }
+
+    /// `primary := "(" expr ")" | leaf`
+    fn parse_primary(&mut self) -> Result<Node, ExprError> {
+        match self.next() {
+            Some((_, Token::LParen)) => {
+                let inner = self.parse_or()?;
+                match self.next() {
+                    Some((_, Token::RParen)) => Ok(inner),
+                    _ => Err(ExprError::new(self.current_offset(), ExprErrorKind::UnclosedParen)),
+                }
+            }
+            Some((offset, Token::Ident(name))) => {
+                let name = name.clone();
+                self.parse_leaf(offset, &name)
+            }
+            Some((offset, token)) => Err(ExprError::new(
+                offset,
+                ExprErrorKind::UnexpectedToken(format!("{:?}", token)),
+            )),
+            None => Err(ExprError::new(self.end_offset, ExprErrorKind::UnexpectedEnd)),
+        }
+    }
+
+    /// Dispatch an identifier to a preset leaf, or consume the rest of a
+    /// `seq(...)` / `score(...) > N` call.
+    fn parse_leaf(&mut self, offset: usize, name: &str) -> Result<Node, ExprError> {
+        match name {
+            "email" => Ok(leaf(PatternRule::email("expr"))),
+            "email_strict" => Ok(leaf(PatternRule::email_strict("expr"))),
+            "url" => Ok(leaf(PatternRule::url("expr"))),
+            "ipv4" => Ok(leaf(PatternRule::ipv4("expr"))),
+            "credit_card" => Ok(leaf(PatternRule::credit_card("expr"))),
+            "seq" => self.parse_seq(offset),
+            "score" => self.parse_score(offset),
+            other => Err(ExprError::new(offset, ExprErrorKind::UnknownLeaf(other.to_string()))),
+        }
+    }
+
+    /// `"seq" "(" STRING ("," STRING)* ")"`
+    fn parse_seq(&mut self, call_offset: usize) -> Result<Node, ExprError> {
+        self.expect(Token::LParen, call_offset)?;
+        let mut tokens = Vec::new();
+        loop {
+            match self.next() {
+                Some((_, Token::String(s))) => tokens.push(s.clone()),
+                _ => return Err(ExprError::new(self.current_offset(), ExprErrorKind::BadSeqArgs)),
+            }
+            match self.peek() {
+                Some((_, Token::Comma)) => {
+                    self.next();
+                }
+                Some((_, Token::RParen)) => {
+                    self.next();
+                    break;
+                }
+                _ => return Err(ExprError::new(self.current_offset(), ExprErrorKind::BadSeqArgs)),
+            }
+        }
+        if tokens.is_empty() {
+            return Err(ExprError::new(call_offset, ExprErrorKind::BadSeqArgs));
+        }
+        Ok(leaf(ForbiddenSequenceRule::with_gaps(tokens, "expr")))
+    }
+
+    /// `"score" "(" IDENT ")" ">" NUMBER`
+    fn parse_score(&mut self, call_offset: usize) -> Result<Node, ExprError> {
+        self.expect(Token::LParen, call_offset)?;
+        match self.next() {
+            Some((_, Token::Ident(_))) => {}
+            _ => return Err(ExprError::new(self.current_offset(), ExprErrorKind::BadSeqArgs)),
+        }
+        self.expect(Token::RParen, call_offset)?;
+        self.expect(Token::Gt, call_offset)?;
+        let threshold = match self.next() {
+            Some((_, Token::Number(n))) => *n,
+            Some((offset, token)) => {
+                return Err(ExprError::new(
+                    offset,
+                    ExprErrorKind::BadScore(format!("{:?}", token)),
+                ))
+            }
+            None => return Err(ExprError::new(self.end_offset, ExprErrorKind::UnexpectedEnd)),
+        };
+        Ok(leaf(ScoreThresholdLeaf::new(threshold)))
+    }
+
+    fn expect(&mut self, want: Token, call_offset: usize) -> Result<(), ExprError> {
+        match self.next() {
+            Some((_, token)) if *token == want => Ok(()),
+            Some((offset, token)) => Err(ExprError::new(
+                offset,
+                ExprErrorKind::UnexpectedToken(format!("{:?}", token)),
+            )),
+            None => Err(ExprError::new(call_offset, ExprErrorKind::UnexpectedEnd)),
+        }
+    }
+}
+
+fn leaf<R: Rule + 'static>(rule: R) -> Node {
+    Node::Leaf(Box::new(rule))
+}
+
+/// Leaf rule for `score(NAME) > N`: blocks once the wrapped Bayesian score
+/// exceeds `threshold`. See the module docs for why `NAME` is accepted but
+/// not yet used to select among multiple scoring sources.
+struct ScoreThresholdLeaf {
+    bayes: BayesRule,
+    threshold: u32,
+}
+
+impl ScoreThresholdLeaf {
+    fn new(threshold: u32) -> Self {
+        Self {
+            bayes: BayesRule::new(),
+            threshold,
+        }
+    }
+}
+
+impl Rule for ScoreThresholdLeaf {
+    fn feed(&mut self, chunk: &str) -> Decision {
+        self.bayes.feed(chunk);
+        if self.bayes.last_score() > self.threshold {
+            Decision::Block {
+                reason: format!("score exceeded {}", self.threshold),
+            }
+        } else {
+            Decision::Allow
+        }
+    }
+
+    fn reset(&mut self) {
+        self.bayes.reset();
+    }
+
+    fn name(&self) -> &str {
+        "score_threshold"
+    }
+
+    fn last_score(&self) -> u32 {
+        self.bayes.last_score()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_and_requires_both_sides() {
+        let mut rule = compile("email AND url").unwrap();
+        assert!(rule.feed("just an email user@example.com").is_allow());
+        rule.reset();
+        assert!(rule
+            .feed("user@example.com and http://example.com")
+            .is_block());
+    }
+
+    #[test]
+    fn test_or_matches_either_side() {
+        let mut rule = compile("email OR url").unwrap();
+        assert!(rule.feed("http://example.com").is_block());
+    }
+
+    #[test]
+    fn test_not_negates() {
+        let mut rule = compile("NOT email").unwrap();
+        assert!(rule.feed("no match here").is_block());
+        rule.reset();
+        assert!(rule.feed("user@example.com").is_allow());
+    }
+
+    #[test]
+    fn test_parens_group_precedence() {
+        let mut rule = compile("(email OR url) AND NOT ipv4").unwrap();
+        assert!(rule.feed("user@example.com").is_block());
+    }
+
+    #[test]
+    fn test_seq_leaf() {
+        let mut rule = compile(r#"seq("how","to","hack")"#).unwrap();
+        assert!(rule.feed("how to hack a server").is_block());
+    }
+
+    #[test]
+    fn test_unknown_leaf_errors_with_offset() {
+        let err = compile("bogus").unwrap_err();
+        assert_eq!(err.offset, 0);
+        assert!(matches!(err.kind, ExprErrorKind::UnknownLeaf(_)));
+    }
+
+    #[test]
+    fn test_unclosed_paren_errors() {
+        let err = compile("(email AND url").unwrap_err();
+        assert!(matches!(err.kind, ExprErrorKind::UnclosedParen));
+    }
+
+    #[test]
+    fn test_score_leaf_parses() {
+        let rule = compile("score(malware) > 50").unwrap();
+        assert_eq!(rule.name(), "expression_rule");
+    }
+}
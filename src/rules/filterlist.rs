@@ -0,0 +1,181 @@
+//! Import rules from an Adblock-Plus-style filter list
+//!
+//! Filter lists are line-oriented text files shared between ad/content
+//! blockers. Unlike the [`compiler`](crate::rules::compiler) DSL, the format
+//! is deliberately tolerant: comments and unrecognized options are skipped
+//! rather than rejected, so parsing never fails -- a filter list with one bad
+//! line still loads the rest, the way a browser extension ignores lines it
+//! doesn't understand instead of refusing the whole list.
+//!
+//! # Supported syntax
+//!
+//! - `! comment` and `[Header]` lines are ignored.
+//! - A plain line is a blocking filter; its words become a strict (no-gap)
+//!   [`ForbiddenSequenceRule`] matched case-insensitively by default.
+//! - An `@@`-prefixed line is an *exception* filter: a match overrides an
+//!   otherwise-blocking decision back to `Decision::Allow` for that chunk,
+//!   the same way `@@` allowlists a would-be-blocked resource in a network
+//!   blocker. See [`GuardEngine::load_filter_list`](crate::GuardEngine::load_filter_list).
+//! - A trailing `$options` suffix is recognized; only `match-case` (opt into
+//!   case-sensitive matching) is understood today, other options
+//!   (`third-party`, `script`, ...) are accepted but ignored since they
+//!   describe a browser/network context this crate doesn't have.
+//! - The domain-anchor markers `||` and `^` and the wildcard `*` are treated
+//!   as plain word separators rather than given real anchor/wildcard
+//!   semantics -- `||example.com^` becomes the single-word phrase
+//!   `example.com`, not a domain match.
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::core::Rule;
+use crate::rules::sequence::{ForbiddenSequenceRule, SequenceConfig};
+
+/// The rules produced by [`parse`]: ordinary blocking rules and the
+/// `@@`-prefixed exception rules that override them.
+pub struct FilterList {
+    /// Rules compiled from plain filter lines.
+    pub rules: Vec<Box<dyn Rule>>,
+    /// Rules compiled from `@@`-prefixed exception lines.
+    pub exceptions: Vec<Box<dyn Rule>>,
+}
+
+/// Parse an Adblock-Plus-style filter list into blocking and exception
+/// rules.
+///
+/// Blank lines, `!` comments, and `[...]` headers are skipped. Every other
+/// non-empty line compiles into a rule; malformed lines (no usable words
+/// after stripping options/anchors) are silently dropped rather than
+/// erroring, matching the format's own tolerant parsing.
+pub fn parse(source: &str) -> FilterList {
+    let mut rules = Vec::new();
+    let mut exceptions = Vec::new();
+
+    for raw in source.lines() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('!') || line.starts_with('[') {
+            continue;
+        }
+
+        let (is_exception, body) = match line.strip_prefix("@@") {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+
+        let (pattern, match_case) = split_options(body);
+        let tokens = tokenize(pattern);
+        if tokens.is_empty() {
+            continue;
+        }
+
+        let config = SequenceConfig::strict().case_insensitive(!match_case);
+        let reason = if is_exception {
+            format!("allowlisted by filter: {}", line)
+        } else {
+            format!("blocked by filter: {}", line)
+        };
+        let rule: Box<dyn Rule> = Box::new(ForbiddenSequenceRule::new(tokens, &reason, config));
+
+        if is_exception {
+            exceptions.push(rule);
+        } else {
+            rules.push(rule);
+        }
+    }
+
+    FilterList { rules, exceptions }
+}
+
+/// Split a trailing `$options` suffix off a filter body, returning the bare
+/// pattern and whether `match-case` was requested.
+fn split_options(body: &str) -> (&str, bool) {
+    match body.split_once('$') {
+        Some((pattern, options)) => {
+            let match_case = options.split(',').any(|o| o.trim() == "match-case");
+            (pattern, match_case)
+        }
+        None => (body, false),
+    }
+}
+
+/// Split a filter pattern into words, stripping the anchor/wildcard markers
+/// this crate gives no special meaning to (see the module docs).
+fn tokenize(pattern: &str) -> Vec<String> {
+    let pattern = pattern.trim_start_matches("||").trim_end_matches('^');
+    pattern
+        .split(|c: char| c.is_whitespace() || c == '*' || c == '^')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Rule;
+
+    #[test]
+    fn test_plain_line_becomes_blocking_rule() {
+        let list = parse("badword");
+        assert_eq!(list.rules.len(), 1);
+        assert_eq!(list.exceptions.len(), 0);
+        assert!(list.rules.into_iter().next().unwrap().feed("badword").is_block());
+    }
+
+    #[test]
+    fn test_exception_line_becomes_exception_rule() {
+        let list = parse("@@goodword");
+        assert_eq!(list.rules.len(), 0);
+        assert_eq!(list.exceptions.len(), 1);
+        assert!(list
+            .exceptions
+            .into_iter()
+            .next()
+            .unwrap()
+            .feed("goodword")
+            .is_block());
+    }
+
+    #[test]
+    fn test_comments_and_headers_skipped() {
+        let list = parse("! this is a comment\n[Adblock Plus 2.0]\nbadword");
+        assert_eq!(list.rules.len(), 1);
+    }
+
+    #[test]
+    fn test_default_is_case_insensitive() {
+        let list = parse("secret phrase");
+        let mut rule = list.rules.into_iter().next().unwrap();
+        assert!(rule.feed("SECRET PHRASE").is_block());
+    }
+
+    #[test]
+    fn test_match_case_option_requires_exact_case() {
+        let list = parse("secret phrase$match-case");
+        let mut rule = list.rules.into_iter().next().unwrap();
+        assert!(rule.feed("SECRET PHRASE").is_allow());
+        assert!(rule.feed("secret phrase").is_block());
+    }
+
+    #[test]
+    fn test_domain_anchors_treated_as_words() {
+        let list = parse("||example.com^");
+        let mut rule = list.rules.into_iter().next().unwrap();
+        assert!(rule.feed("example.com").is_block());
+    }
+
+    #[test]
+    fn test_unrecognized_option_is_ignored_not_rejected() {
+        let list = parse("badword$third-party,script");
+        assert_eq!(list.rules.len(), 1);
+    }
+
+    #[test]
+    fn test_empty_list_produces_no_rules() {
+        let list = parse("! just a comment\n\n");
+        assert_eq!(list.rules.len(), 0);
+        assert_eq!(list.exceptions.len(), 0);
+    }
+}
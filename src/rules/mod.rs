@@ -1,7 +1,30 @@
 //! Rule implementations
 
+pub mod ahocorasick;
+pub mod bayes;
+pub mod compiler;
+pub mod composite;
+pub mod expr;
+pub mod filterlist;
 pub mod pattern;
+#[cfg(feature = "process")]
+pub mod process;
+#[cfg(feature = "regex")]
+pub mod regex_rule;
+#[cfg(feature = "config")]
+pub mod rulepack;
 pub mod sequence;
 
-pub use pattern::{PatternConfig, PatternPreset, PatternRule};
+pub use ahocorasick::ForbiddenSetRule;
+pub use bayes::{BayesModelError, BayesRule};
+pub use composite::CompositeRule;
+pub use pattern::{
+    CardBrand, DiscardPolicy, PatternConfig, PatternPreset, PatternRule, SchemePolicy, UnknownRuleKind,
+};
+#[cfg(feature = "process")]
+pub use process::{FailurePolicy, ProcessRule};
+#[cfg(feature = "regex")]
+pub use regex_rule::{RegexCompileError, RegexRule, RegexRuleBuilder};
+#[cfg(feature = "config")]
+pub use rulepack::{NamedRule, RulePack, RulePackEntry, RulePackError, RulePackKind};
 pub use sequence::{ForbiddenSequenceRule, SequenceConfig};
@@ -18,15 +18,123 @@
 //! optimized for that specific pattern type. This is sufficient for common
 //! guardrail use cases and aligns with the project's IDS-inspired approach.
 //!
-//! # Future Enhancements
+//! # Custom patterns beyond the presets
 //!
-//! A full DFA-based regex engine could be added as an optional feature
-//! (see TODO.md), but the current implementation satisfies the core requirements.
+//! [`PatternRule::custom`] only does literal keyword matching -- it has no
+//! hand-coded matcher to dispatch to, because there's no single shape to
+//! hand-code for arbitrary user detectors (API keys, JWTs, SSNs, ...). For
+//! those, see [`crate::rules::RegexRule`] behind the optional `regex`
+//! feature: a real, DFA-based regex engine for exactly the cases this
+//! module's presets don't cover, without giving up the "no backtracking"
+//! guarantee from the crate's top-level docs.
 
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
-use crate::core::{Decision, Rule};
+use crate::core::{Decision, MatchComponents, MatchInfo, Rule};
+
+/// Which hand-coded matcher a [`PatternRule`] dispatches to.
+///
+/// `matches_pattern` used to re-derive this from `config.description` via a
+/// `.contains()` chain on every `feed`. This crate has no actual regex or
+/// automaton to compile (see the module doc above), so there's nothing
+/// expensive to cache here -- but resolving it once and reusing it still
+/// avoids repeating that string scan on every chunk, and the idle-discard
+/// behavior below means a `GuardEngine` holding many `PatternRule`s doesn't
+/// keep every one resolved if most have gone quiet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MatcherKind {
+    /// Email pattern (simple or strict; both dispatch the same matcher).
+    Email,
+    /// URL pattern (`http(s)://` only).
+    Url,
+    /// Generic URI pattern (any `scheme:` or `scheme://`).
+    Uri,
+    /// IPv4 pattern.
+    Ipv4,
+    /// Credit card pattern.
+    CreditCard,
+    /// Custom / fallback substring pattern.
+    Custom,
+}
+
+impl MatcherKind {
+    /// Resolve a matcher kind from a description string, mirroring the
+    /// dispatch `matches_pattern` previously re-ran every call.
+    fn from_description(description: &str) -> Self {
+        if description.contains("email") {
+            MatcherKind::Email
+        } else if description.contains("URI") {
+            MatcherKind::Uri
+        } else if description.contains("URL") {
+            MatcherKind::Url
+        } else if description.contains("IPv4") {
+            MatcherKind::Ipv4
+        } else if description.contains("credit card") {
+            MatcherKind::CreditCard
+        } else {
+            MatcherKind::Custom
+        }
+    }
+}
+
+/// Budget governing how long a [`PatternRule`] keeps its resolved
+/// [`MatcherKind`] before discarding it as idle.
+///
+/// `max_live` is accepted for forward compatibility with a future shared
+/// matcher registry (see `GuardEngine::add_pattern_rule`); today each rule
+/// tracks its own resolved matcher independently, so only `idle_threshold`
+/// has an effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiscardPolicy {
+    /// Reserved budget on the number of rules with a live resolved matcher.
+    pub max_live: usize,
+    /// Number of feeds a resolved matcher may go unused before it is
+    /// discarded and re-resolved (cheaply) on the next feed.
+    pub idle_threshold: u64,
+}
+
+impl Default for DiscardPolicy {
+    fn default() -> Self {
+        Self {
+            max_live: 256,
+            idle_threshold: 1000,
+        }
+    }
+}
+
+/// Lazily-resolved, idle-discarding cache of a single rule's [`MatcherKind`].
+#[derive(Debug, Clone, Copy)]
+struct MatcherSlot {
+    resolved: Option<MatcherKind>,
+    last_used_at: u64,
+    idle_threshold: u64,
+}
+
+impl MatcherSlot {
+    fn new(idle_threshold: u64) -> Self {
+        Self {
+            resolved: None,
+            last_used_at: 0,
+            idle_threshold,
+        }
+    }
+
+    /// Return the resolved kind for `description`, computing and caching it
+    /// on first use or after an idle discard.
+    fn resolve(&mut self, description: &str, tick: u64) -> MatcherKind {
+        if let Some(kind) = self.resolved {
+            if tick.saturating_sub(self.last_used_at) <= self.idle_threshold {
+                self.last_used_at = tick;
+                return kind;
+            }
+        }
+        let kind = MatcherKind::from_description(description);
+        self.resolved = Some(kind);
+        self.last_used_at = tick;
+        kind
+    }
+}
 
 /// Preset pattern types for common use cases
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -37,10 +145,194 @@ pub enum PatternPreset {
     EmailStrict,
     /// URLs (http/https)
     Url,
+    /// Generic URIs -- any `scheme:` or `scheme://` form, e.g. `mailto:`,
+    /// `ftp://`, or a custom scheme, not just `http(s)://`.
+    Uri,
     /// IPv4 addresses
     Ipv4,
     /// Credit card numbers (basic format)
     CreditCard,
+    /// Credit card numbers validated with the Luhn checksum and brand detection
+    CreditCardStrict,
+}
+
+/// Major card brands identified from the leading digits (IIN) and length
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardBrand {
+    /// Visa (prefix 4; length 13/16/19)
+    Visa,
+    /// Mastercard (51–55 or 2221–2720; length 16)
+    Mastercard,
+    /// American Express (34/37; length 15)
+    Amex,
+    /// Discover (6011 / 65 / 644–649; length 16)
+    Discover,
+    /// Diners Club (300–305 / 36 / 38; length 14)
+    Diners,
+}
+
+impl CardBrand {
+    /// Human-readable brand name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CardBrand::Visa => "Visa",
+            CardBrand::Mastercard => "Mastercard",
+            CardBrand::Amex => "Amex",
+            CardBrand::Discover => "Discover",
+            CardBrand::Diners => "Diners Club",
+        }
+    }
+
+    /// Classify a digit string by its IIN prefix and length.
+    ///
+    /// Returns `None` when the number matches no known brand.
+    pub fn detect(digits: &str) -> Option<CardBrand> {
+        let len = digits.len();
+        let prefix = |n: usize| -> u32 { digits[..n.min(len)].parse().unwrap_or(0) };
+
+        // Visa: leading 4.
+        if digits.starts_with('4') && matches!(len, 13 | 16 | 19) {
+            return Some(CardBrand::Visa);
+        }
+        // Amex: 34 / 37, length 15.
+        if len == 15 && (digits.starts_with("34") || digits.starts_with("37")) {
+            return Some(CardBrand::Amex);
+        }
+        // Diners: 300–305 / 36 / 38, length 14.
+        if len == 14
+            && (digits.starts_with("36")
+                || digits.starts_with("38")
+                || (300..=305).contains(&prefix(3)))
+        {
+            return Some(CardBrand::Diners);
+        }
+        if len == 16 {
+            // Mastercard: 51–55 or 2221–2720.
+            let p2 = prefix(2);
+            let p4 = prefix(4);
+            if (51..=55).contains(&p2) || (2221..=2720).contains(&p4) {
+                return Some(CardBrand::Mastercard);
+            }
+            // Discover: 6011 / 65 / 644–649.
+            if digits.starts_with("6011")
+                || digits.starts_with("65")
+                || (644..=649).contains(&prefix(3))
+            {
+                return Some(CardBrand::Discover);
+            }
+        }
+        None
+    }
+}
+
+/// Validate a digit string with the Luhn (mod 10) checksum.
+///
+/// Starting from the rightmost digit, every second digit is doubled; a doubled
+/// value over 9 has 9 subtracted. The number is valid iff the total sum is
+/// divisible by 10.
+fn luhn_valid(digits: &str) -> bool {
+    if digits.is_empty() {
+        return false;
+    }
+    let mut sum = 0u32;
+    for (i, c) in digits.chars().rev().enumerate() {
+        let Some(d) = c.to_digit(10) else {
+            return false;
+        };
+        if i % 2 == 1 {
+            let doubled = d * 2;
+            sum += if doubled > 9 { doubled - 9 } else { doubled };
+        } else {
+            sum += d;
+        }
+    }
+    sum % 10 == 0
+}
+
+/// Reject placeholder-style candidates (e.g. `"0000000000000000"`) that can
+/// pass the Luhn checksum by coincidence but are never real card numbers.
+fn all_same_digit(digits: &str) -> bool {
+    match digits.as_bytes().first() {
+        Some(&first) => digits.bytes().all(|b| b == first),
+        None => false,
+    }
+}
+
+/// Mask all but the last 4 digits of a matched card-number candidate,
+/// preserving its original digit grouping and separators.
+fn mask_credit_card(matched: &str) -> String {
+    let digit_count = matched.chars().filter(|c| c.is_ascii_digit()).count();
+    let mut seen = 0usize;
+    matched
+        .chars()
+        .map(|c| {
+            if !c.is_ascii_digit() {
+                return c;
+            }
+            seen += 1;
+            if seen > digit_count.saturating_sub(4) {
+                c
+            } else {
+                '*'
+            }
+        })
+        .collect()
+}
+
+/// Characters allowed in an RFC 5322 dot-atom local part, plus `.` (the
+/// leading/trailing/consecutive-dot rules are checked separately).
+fn is_local_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+/=?^_`{|}~.-".contains(c)
+}
+
+/// Characters allowed within a domain (labels plus the separating dots).
+fn is_domain_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '.'
+}
+
+/// Validate a `local@domain` candidate against RFC 5322-grade length and
+/// character-class rules, rejecting near-miss shapes the loose scan lets
+/// through. See [`PatternRule::email_strict`].
+fn validate_email_rfc(candidate: &str) -> bool {
+    if candidate.len() > 254 {
+        return false;
+    }
+
+    let Some((local, domain)) = candidate.split_once('@') else {
+        return false;
+    };
+
+    if local.is_empty() || local.len() > 64 {
+        return false;
+    }
+    if local.starts_with('.') || local.ends_with('.') || local.contains("..") {
+        return false;
+    }
+    if !local.chars().all(is_local_char) {
+        return false;
+    }
+
+    if domain.is_empty() || domain.len() > 255 {
+        return false;
+    }
+    let labels: Vec<&str> = domain.split('.').collect();
+    if labels.len() < 2 {
+        return false;
+    }
+    for label in &labels {
+        if label.is_empty() || label.len() > 63 {
+            return false;
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            return false;
+        }
+        if !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return false;
+        }
+    }
+
+    let tld = labels.last().expect("labels has at least 2 entries");
+    tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic())
 }
 
 impl PatternPreset {
@@ -60,12 +352,18 @@ impl PatternPreset {
             
             // URLs starting with http:// or https://
             PatternPreset::Url => r"https?://[a-zA-Z0-9.-]+(?:\.[a-zA-Z]{2,})+(?:/[^\s]*)?",
-            
+
+            // Any `scheme:` or `scheme://`, e.g. `mailto:`, `ftp://`, a custom scheme
+            PatternPreset::Uri => r"[a-zA-Z][a-zA-Z0-9+.-]+:(?://\S+|\S+)",
+
             // IPv4: xxx.xxx.xxx.xxx
             PatternPreset::Ipv4 => r"\b(?:[0-9]{1,3}\.){3}[0-9]{1,3}\b",
             
             // Credit card: groups of 4 digits
             PatternPreset::CreditCard => r"\b(?:\d{4}[- ]?){3}\d{4}\b",
+
+            // Same shape as CreditCard; validation differs at match time
+            PatternPreset::CreditCardStrict => r"\b(?:\d{4}[- ]?){3}\d{4}\b",
         }
     }
 
@@ -75,8 +373,68 @@ impl PatternPreset {
             PatternPreset::Email => "email address",
             PatternPreset::EmailStrict => "email address (strict)",
             PatternPreset::Url => "URL",
+            PatternPreset::Uri => "URI",
             PatternPreset::Ipv4 => "IPv4 address",
             PatternPreset::CreditCard => "credit card number",
+            PatternPreset::CreditCardStrict => "credit card number (strict)",
+        }
+    }
+}
+
+/// Returned by [`PatternPreset`]'s `FromStr` impl when the name doesn't match
+/// any known preset, so config loaders (e.g. [`crate::config`]) can report
+/// which kind string was rejected instead of silently falling back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownRuleKind(pub String);
+
+impl core::str::FromStr for PatternPreset {
+    type Err = UnknownRuleKind;
+
+    /// Parse a config-facing kind name into a preset.
+    ///
+    /// Recognizes `"email"`, `"email_strict"`, `"url"`, `"uri"`, `"ipv4"`,
+    /// `"credit_card"`, and `"credit_card_strict"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "email" => Ok(PatternPreset::Email),
+            "email_strict" => Ok(PatternPreset::EmailStrict),
+            "url" => Ok(PatternPreset::Url),
+            "uri" => Ok(PatternPreset::Uri),
+            "ipv4" => Ok(PatternPreset::Ipv4),
+            "credit_card" => Ok(PatternPreset::CreditCard),
+            "credit_card_strict" => Ok(PatternPreset::CreditCardStrict),
+            other => Err(UnknownRuleKind(other.to_string())),
+        }
+    }
+}
+
+/// Which schemes a [`PatternPreset::Uri`] rule treats as a match.
+///
+/// Only consulted by the `Uri` matcher; other presets ignore it. This is
+/// what lets a rule flag exfiltration-prone schemes (`mailto:`, `ftp:`)
+/// while letting ordinary browser links (`https:`) through, or vice versa.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemePolicy {
+    /// Every scheme matches.
+    Any,
+    /// Only the listed schemes are safe (case-insensitive); every other
+    /// scheme is flagged as a match. Use this when a handful of schemes are
+    /// known-good and anything else should be treated with suspicion.
+    Allow(Vec<String>),
+    /// The listed schemes are flagged as a match (case-insensitive); every
+    /// other scheme is safe. The inverse of `Allow` -- use this to flag a
+    /// few risky schemes (e.g. `mailto`, `ftp`) without naming every safe
+    /// one.
+    Deny(Vec<String>),
+}
+
+impl SchemePolicy {
+    /// Whether `scheme` should be treated as a match under this policy.
+    fn matches(&self, scheme: &str) -> bool {
+        match self {
+            SchemePolicy::Any => true,
+            SchemePolicy::Allow(schemes) => !schemes.iter().any(|s| s.eq_ignore_ascii_case(scheme)),
+            SchemePolicy::Deny(schemes) => schemes.iter().any(|s| s.eq_ignore_ascii_case(scheme)),
         }
     }
 }
@@ -90,6 +448,10 @@ pub struct PatternConfig {
     description: String,
     /// Whether to use case-insensitive matching
     case_insensitive: bool,
+    /// Whether credit-card matches must pass the Luhn checksum before blocking
+    require_checksum: bool,
+    /// Which schemes a `Uri` match is restricted to. Ignored by every other preset.
+    scheme_policy: SchemePolicy,
 }
 
 impl PatternConfig {
@@ -99,6 +461,9 @@ impl PatternConfig {
             pattern: preset.pattern().to_string(),
             description: preset.description().to_string(),
             case_insensitive: false,
+            // The strict credit-card preset validates the checksum by default.
+            require_checksum: preset == PatternPreset::CreditCardStrict,
+            scheme_policy: SchemePolicy::Any,
         }
     }
 
@@ -108,6 +473,8 @@ impl PatternConfig {
             pattern: pattern.to_string(),
             description: description.to_string(),
             case_insensitive: false,
+            require_checksum: false,
+            scheme_policy: SchemePolicy::Any,
         }
     }
 
@@ -117,6 +484,23 @@ impl PatternConfig {
         self
     }
 
+    /// Require a valid Luhn checksum before a credit-card match blocks.
+    ///
+    /// When enabled, a candidate that fails the checksum is treated as a
+    /// non-match (the rule returns `Allow`), cutting false positives on
+    /// invoice numbers and tracking IDs.
+    pub fn require_checksum(mut self, enabled: bool) -> Self {
+        self.require_checksum = enabled;
+        self
+    }
+
+    /// Restrict a [`PatternPreset::Uri`] rule to only match specific
+    /// schemes (see [`SchemePolicy`]). Ignored by every other preset.
+    pub fn scheme_policy(mut self, policy: SchemePolicy) -> Self {
+        self.scheme_policy = policy;
+        self
+    }
+
     /// Get the pattern string
     pub fn pattern(&self) -> &str {
         &self.pattern
@@ -165,6 +549,23 @@ pub struct PatternRule {
     reason: String,
     /// Replacement text for rewrites (None = block mode)
     replacement: Option<String>,
+    /// Optional rewrite template with `$`-placeholders filled from the match
+    template: Option<String>,
+    /// Lazily-resolved, idle-discarding cache of which matcher this rule
+    /// dispatches to.
+    matcher_slot: MatcherSlot,
+    /// Number of `feed` calls so far, used as the tick for idle discard.
+    feed_count: u64,
+    /// Scratch buffer reused across `feed` calls for case-folded text, so
+    /// case-insensitive matching doesn't allocate a fresh `String` every chunk.
+    scratch: String,
+    /// Byte offset of `buffer[0]` within the cumulative stream this rule has
+    /// been fed, so match spans recorded in [`Self::last_match_info`] can be
+    /// reported relative to the whole stream, not just the current buffer.
+    buffer_start: usize,
+    /// Structured detail about the most recent match, if the active matcher
+    /// knows how to decompose it. See [`Rule::last_match_info`].
+    last_match_info: Option<MatchInfo>,
 }
 
 impl PatternRule {
@@ -173,6 +574,12 @@ impl PatternRule {
         Self {
             config: PatternConfig::from_preset(preset),
             buffer: String::new(),
+            matcher_slot: MatcherSlot::new(DiscardPolicy::default().idle_threshold),
+            feed_count: 0,
+            scratch: String::new(),
+            buffer_start: 0,
+            last_match_info: None,
+            template: None,
             reason: reason.to_string(),
             replacement: None,
         }
@@ -184,15 +591,52 @@ impl PatternRule {
     }
 
     /// Create a strict email detection rule
+    ///
+    /// Runs a genuinely RFC 5322-grade check (length limits, character
+    /// classes, dot and hyphen placement) on the reassembled candidate,
+    /// rather than the loose "alphanumeric before an `@`, a dot after it"
+    /// shape used by [`Self::email`].
     pub fn email_strict(reason: &str) -> Self {
         Self::from_preset(PatternPreset::EmailStrict, reason)
     }
 
-    /// Create a URL detection rule
+    /// Create a URL detection rule (`http(s)://` only)
     pub fn url(reason: &str) -> Self {
         Self::from_preset(PatternPreset::Url, reason)
     }
 
+    /// Create a URI detection rule recognizing any `scheme:` or
+    /// `scheme://` form -- `mailto:`, `ftp://`, a custom scheme -- not just
+    /// `http(s)://` like [`Self::url`].
+    ///
+    /// Every scheme matches by default; combine with
+    /// [`Self::uri_with_scheme_policy`] to flag only specific schemes
+    /// (e.g. `mailto` and `ftp`) while letting others (`https`) through.
+    pub fn uri(reason: &str) -> Self {
+        Self::from_preset(PatternPreset::Uri, reason)
+    }
+
+    /// Create a URI detection rule restricted to the given [`SchemePolicy`].
+    ///
+    /// ```rust
+    /// use streamguard::rules::{PatternRule, SchemePolicy};
+    /// use streamguard::Rule;
+    ///
+    /// // Flag mailto/ftp links, but let https through untouched.
+    /// let mut rule = PatternRule::uri_with_scheme_policy(
+    ///     "embedded link uses a risky scheme",
+    ///     SchemePolicy::Deny(vec!["mailto".to_string(), "ftp".to_string()]),
+    /// );
+    /// assert!(rule.feed("see ftp://files.example.com/data").is_block());
+    /// rule.reset();
+    /// assert!(rule.feed("see https://example.com").is_allow());
+    /// ```
+    pub fn uri_with_scheme_policy(reason: &str, policy: SchemePolicy) -> Self {
+        let mut rule = Self::from_preset(PatternPreset::Uri, reason);
+        rule.config = rule.config.scheme_policy(policy);
+        rule
+    }
+
     /// Create an IPv4 detection rule
     pub fn ipv4(reason: &str) -> Self {
         Self::from_preset(PatternPreset::Ipv4, reason)
@@ -203,11 +647,25 @@ impl PatternRule {
         Self::from_preset(PatternPreset::CreditCard, reason)
     }
 
+    /// Create a credit card detection rule that validates the Luhn checksum
+    ///
+    /// Candidates that fail the checksum are allowed through rather than
+    /// blocked, and the detected brand is appended to the block reason.
+    pub fn credit_card_strict(reason: &str) -> Self {
+        Self::from_preset(PatternPreset::CreditCardStrict, reason)
+    }
+
     /// Create an email rewrite rule
     pub fn email_rewrite(replacement: &str) -> Self {
         Self {
             config: PatternConfig::from_preset(PatternPreset::Email),
             buffer: String::new(),
+            matcher_slot: MatcherSlot::new(DiscardPolicy::default().idle_threshold),
+            feed_count: 0,
+            scratch: String::new(),
+            buffer_start: 0,
+            last_match_info: None,
+            template: None,
             reason: "email redacted".to_string(),
             replacement: Some(replacement.to_string()),
         }
@@ -218,6 +676,12 @@ impl PatternRule {
         Self {
             config: PatternConfig::from_preset(PatternPreset::Url),
             buffer: String::new(),
+            matcher_slot: MatcherSlot::new(DiscardPolicy::default().idle_threshold),
+            feed_count: 0,
+            scratch: String::new(),
+            buffer_start: 0,
+            last_match_info: None,
+            template: None,
             reason: "url redacted".to_string(),
             replacement: Some(replacement.to_string()),
         }
@@ -228,6 +692,12 @@ impl PatternRule {
         Self {
             config: PatternConfig::from_preset(PatternPreset::Ipv4),
             buffer: String::new(),
+            matcher_slot: MatcherSlot::new(DiscardPolicy::default().idle_threshold),
+            feed_count: 0,
+            scratch: String::new(),
+            buffer_start: 0,
+            last_match_info: None,
+            template: None,
             reason: "ip redacted".to_string(),
             replacement: Some(replacement.to_string()),
         }
@@ -238,26 +708,141 @@ impl PatternRule {
         Self {
             config: PatternConfig::from_preset(PatternPreset::CreditCard),
             buffer: String::new(),
+            matcher_slot: MatcherSlot::new(DiscardPolicy::default().idle_threshold),
+            feed_count: 0,
+            scratch: String::new(),
+            buffer_start: 0,
+            last_match_info: None,
+            template: None,
             reason: "card redacted".to_string(),
             replacement: Some(replacement.to_string()),
         }
     }
 
+    /// Create a rewrite rule that masks part of the match from a template
+    ///
+    /// Unlike the `*_rewrite` constructors, which swap the whole match for a
+    /// fixed string, the template can reference sub-spans of the match so a
+    /// value is partially redacted instead of fully erased. Placeholders are
+    /// filled from the matched text once the pattern completes:
+    ///
+    /// - credit cards: `$last4` (the final four digits)
+    /// - emails: `$local`, `$domain` (the parts either side of `@`)
+    /// - URLs: `$scheme`, `$host`, `$path`
+    ///
+    /// ```rust
+    /// use streamguard::rules::{PatternRule, PatternPreset};
+    /// use streamguard::{Rule, Decision};
+    ///
+    /// let mut rule = PatternRule::with_rewrite_template(
+    ///     PatternPreset::CreditCard,
+    ///     "**** **** **** $last4",
+    /// );
+    /// match rule.feed("Card: 4111111111111111") {
+    ///     Decision::Rewrite { replacement } => assert!(replacement.ends_with("1111")),
+    ///     _ => panic!("expected rewrite"),
+    /// }
+    /// ```
+    pub fn with_rewrite_template(preset: PatternPreset, template: &str) -> Self {
+        Self {
+            config: PatternConfig::from_preset(preset),
+            buffer: String::new(),
+            matcher_slot: MatcherSlot::new(DiscardPolicy::default().idle_threshold),
+            feed_count: 0,
+            scratch: String::new(),
+            buffer_start: 0,
+            last_match_info: None,
+            template: Some(template.to_string()),
+            reason: "redacted".to_string(),
+            replacement: Some(String::new()),
+        }
+    }
+
     /// Create a custom pattern rule with full configuration
     pub fn custom(pattern: &str, reason: &str, description: &str) -> Self {
         Self {
             config: PatternConfig::custom(pattern, description),
             buffer: String::new(),
+            matcher_slot: MatcherSlot::new(DiscardPolicy::default().idle_threshold),
+            feed_count: 0,
+            scratch: String::new(),
+            buffer_start: 0,
+            last_match_info: None,
+            template: None,
             reason: reason.to_string(),
             replacement: None,
         }
     }
 
+    /// Fold case while matching so patterns match regardless of case.
+    pub fn case_insensitive(mut self) -> Self {
+        self.config = self.config.case_insensitive(true);
+        self
+    }
+
+    /// Switch this rule into redact mode: a match is replaced with fixed
+    /// text and the stream keeps flowing, instead of blocking.
+    ///
+    /// This is chaining sugar over the separate `*_rewrite` constructors
+    /// (e.g. [`Self::email_rewrite`]) for callers who already built the rule
+    /// from a preset:
+    ///
+    /// ```rust
+    /// use streamguard::rules::PatternRule;
+    /// use streamguard::Rule;
+    ///
+    /// let mut rule = PatternRule::email("found email").redact("[REDACTED]");
+    /// assert!(rule.feed("contact user@example.com").is_rewrite());
+    /// ```
+    ///
+    /// Only the email, URL, IPv4 and credit-card matchers rewrite the matched
+    /// text; a URI or custom rule in redact mode hands the matched text back
+    /// unchanged.
+    pub fn redact(mut self, replacement: &str) -> Self {
+        self.template = None;
+        self.replacement = Some(replacement.to_string());
+        self
+    }
+
+    /// Switch this rule into redact mode using a partial-mask template (see
+    /// [`Self::with_rewrite_template`]), chainable onto a preset
+    /// constructor the same way [`Self::redact`] is.
+    ///
+    /// ```rust
+    /// use streamguard::rules::{PatternRule, PatternPreset};
+    /// use streamguard::Rule;
+    ///
+    /// let mut rule = PatternRule::from_preset(PatternPreset::CreditCard, "card found")
+    ///     .redact_masked("**** **** **** $last4");
+    /// match rule.feed("Card: 4111111111111111") {
+    ///     streamguard::Decision::Rewrite { replacement } => assert!(replacement.ends_with("1111")),
+    ///     _ => panic!("expected rewrite"),
+    /// }
+    /// ```
+    pub fn redact_masked(mut self, template: &str) -> Self {
+        self.template = Some(template.to_string());
+        self.replacement = Some(String::new());
+        self
+    }
+
+    /// Override how long this rule keeps its resolved matcher before
+    /// discarding it as idle. See [`GuardEngine::add_pattern_rule`](crate::GuardEngine::add_pattern_rule).
+    pub fn with_discard_policy(mut self, policy: DiscardPolicy) -> Self {
+        self.matcher_slot = MatcherSlot::new(policy.idle_threshold);
+        self
+    }
+
     /// Create a custom rule with configuration
     pub fn with_config(config: PatternConfig, reason: &str) -> Self {
         Self {
             config,
             buffer: String::new(),
+            matcher_slot: MatcherSlot::new(DiscardPolicy::default().idle_threshold),
+            feed_count: 0,
+            scratch: String::new(),
+            buffer_start: 0,
+            last_match_info: None,
+            template: None,
             reason: reason.to_string(),
             replacement: None,
         }
@@ -270,46 +855,182 @@ impl PatternRule {
     /// - Predictable O(n) performance
     /// - Easy security auditing
     /// - Deterministic behavior across all platforms
-    fn matches_pattern(&self, text: &str) -> bool {
-        let search_text = if self.config.case_insensitive {
-            text.to_lowercase()
+    fn matches_pattern(&mut self, text: &str) -> bool {
+        // Resolve (or reuse) which matcher applies, instead of re-running the
+        // `.contains()` dispatch below on every chunk.
+        let kind = self
+            .matcher_slot
+            .resolve(&self.config.description, self.feed_count);
+
+        // Reuse the scratch buffer for case folding instead of allocating a
+        // fresh `String` every feed.
+        self.scratch.clear();
+        let search_text: &str = if self.config.case_insensitive {
+            self.scratch.extend(text.chars().flat_map(char::to_lowercase));
+            &self.scratch
         } else {
-            text.to_string()
+            text
         };
 
-        // Dispatch to appropriate specialized matcher based on description
-        // Using description matching for now; could use enum in future refactor
-        match self.config.description.as_str() {
-            desc if desc.contains("email") => self.check_email_pattern(&search_text),
-            desc if desc.contains("URL") => self.check_url_pattern(&search_text),
-            desc if desc.contains("IPv4") => self.check_ipv4_pattern(&search_text),
-            desc if desc.contains("credit card") => self.check_credit_card_pattern(&search_text),
-            _ => {
+        let (matched, info) = match kind {
+            MatcherKind::Email => self.check_email_pattern(search_text),
+            MatcherKind::Url => self.check_url_pattern(search_text),
+            MatcherKind::Uri => self.check_uri_pattern(search_text),
+            MatcherKind::Ipv4 => (self.check_ipv4_pattern(search_text), None),
+            MatcherKind::CreditCard => self.check_credit_card_pattern(search_text),
+            MatcherKind::Custom => {
                 // Fallback for custom patterns: simple substring search
                 let pattern_text = if self.config.case_insensitive {
                     self.config.pattern.to_lowercase()
                 } else {
                     self.config.pattern.clone()
                 };
-                search_text.contains(&pattern_text)
+                (search_text.contains(&pattern_text), None)
             }
+        };
+
+        if matched {
+            self.last_match_info = info;
         }
+        matched
     }
 
     /// Check for URL pattern with http:// or https://
-    fn check_url_pattern(&self, text: &str) -> bool {
+    ///
+    /// On a match, also splits the URL into scheme/host/path: host runs
+    /// until the first `/` or whitespace, and path is whatever non-whitespace
+    /// follows.
+    fn check_url_pattern(&self, text: &str) -> (bool, Option<MatchInfo>) {
         // Must contain protocol and domain
         if let Some(proto_pos) = text.find("http://").or_else(|| text.find("https://")) {
-            let after_proto = if text[proto_pos..].starts_with("https://") {
-                &text[proto_pos + 8..]
+            let is_https = text[proto_pos..].starts_with("https://");
+            let scheme_len = if is_https { 8 } else { 7 };
+            let after_proto = &text[proto_pos + scheme_len..];
+
+            // Must have at least some domain content after protocol
+            if after_proto.is_empty() || !after_proto.chars().next().map_or(false, |c| c.is_alphanumeric()) {
+                return (false, None);
+            }
+
+            let host_end = after_proto
+                .find(|c: char| c == '/' || c.is_whitespace())
+                .unwrap_or(after_proto.len());
+            let host = &after_proto[..host_end];
+            let rest = &after_proto[host_end..];
+            let path_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let path = &rest[..path_end];
+
+            let end = proto_pos + scheme_len + host_end + path_end;
+            let info = MatchInfo {
+                rule: self.config.description.clone(),
+                span: (self.buffer_start + proto_pos, self.buffer_start + end),
+                components: MatchComponents::Url {
+                    scheme: (if is_https { "https" } else { "http" }).to_string(),
+                    host: host.to_string(),
+                    path: path.to_string(),
+                },
+            };
+            return (true, Some(info));
+        }
+        (false, None)
+    }
+
+    /// Check for a generic `scheme:` or `scheme://` URI -- `mailto:`,
+    /// `ftp://`, a custom scheme, anything [`Self::check_url_pattern`]
+    /// doesn't recognize because it isn't `http(s)`.
+    ///
+    /// A candidate scheme is an RFC 3986 scheme token (letter, then
+    /// letters/digits/`+`/`-`/`.`) immediately followed by `:` with
+    /// something other than whitespace right after -- this rules out
+    /// ordinary prose like `"Note: see below"`. `scheme://` is parsed into
+    /// host/path the same way [`Self::check_url_pattern`] does; the bare
+    /// `scheme:rest` form used by e.g. `mailto:` has no authority, so `host`
+    /// is empty and `rest` becomes the whole path. Percent-sequences are
+    /// left escaped and dot segments are not collapsed -- this is a
+    /// detector, not a normalizer.
+    ///
+    /// On a match, [`PatternConfig::scheme_policy`] decides whether the
+    /// scheme itself should count as a hit; a candidate whose scheme the
+    /// policy does not flag is skipped and the scan moves on to the next one.
+    fn check_uri_pattern(&self, text: &str) -> (bool, Option<MatchInfo>) {
+        let mut offset = 0;
+        while let Some((start, end, scheme, host, path)) = Self::find_uri(&text[offset..]) {
+            if self.config.scheme_policy.matches(&scheme) {
+                let info = MatchInfo {
+                    rule: self.config.description.clone(),
+                    span: (
+                        self.buffer_start + offset + start,
+                        self.buffer_start + offset + end,
+                    ),
+                    components: MatchComponents::Url { scheme, host, path },
+                };
+                return (true, Some(info));
+            }
+            offset += end;
+        }
+        (false, None)
+    }
+
+    /// Whether `c` can appear in an RFC 3986 scheme token after its first
+    /// (always-alphabetic) character.
+    fn is_scheme_char(c: char) -> bool {
+        c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.'
+    }
+
+    /// Scan `text` for the first `scheme:` or `scheme://` candidate.
+    ///
+    /// Returns the match's `(start, end)` byte span plus its parsed
+    /// scheme/host/path. Walks forward looking for `:`, then backward over
+    /// scheme characters to find where the candidate starts; this is
+    /// O(n * max scheme length) rather than a single linear pass, but scheme
+    /// tokens are short (a handful of bytes) so it stays effectively linear
+    /// in practice.
+    fn find_uri(text: &str) -> Option<(usize, usize, String, String, String)> {
+        let bytes = text.as_bytes();
+        for i in 0..bytes.len() {
+            if bytes[i] != b':' {
+                continue;
+            }
+            let mut start = i;
+            while start > 0 && Self::is_scheme_char(bytes[start - 1] as char) {
+                start -= 1;
+            }
+            let scheme = &text[start..i];
+            if scheme.len() < 2 || !scheme.chars().next().map_or(false, |c| c.is_ascii_alphabetic()) {
+                continue;
+            }
+
+            let after_colon = &text[i + 1..];
+            let (has_authority, rest) = match after_colon.strip_prefix("//") {
+                Some(rest) => (true, rest),
+                None => (false, after_colon),
+            };
+            // Whatever follows must start immediately, with no whitespace --
+            // otherwise this is prose (`"Note: ..."`) rather than a URI.
+            if !rest.chars().next().map_or(false, |c| !c.is_whitespace()) {
+                continue;
+            }
+
+            let (host, path, consumed) = if has_authority {
+                let host_end = rest
+                    .find(|c: char| c == '/' || c.is_whitespace())
+                    .unwrap_or(rest.len());
+                let after_host = &rest[host_end..];
+                let path_end = after_host.find(char::is_whitespace).unwrap_or(after_host.len());
+                (
+                    rest[..host_end].to_string(),
+                    after_host[..path_end].to_string(),
+                    2 + host_end + path_end,
+                )
             } else {
-                &text[proto_pos + 7..]
+                let path_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                (String::new(), rest[..path_end].to_string(), path_end)
             };
-            
-            // Must have at least some domain content after protocol
-            return !after_proto.is_empty() && after_proto.chars().next().map_or(false, |c| c.is_alphanumeric());
+
+            let end = i + 1 + consumed;
+            return Some((start, end, scheme.to_string(), host, path));
         }
-        false
+        None
     }
 
     /// Check for email pattern with improved accuracy
@@ -319,22 +1040,30 @@ impl PatternRule {
     /// - Must have domain part after @
     /// - Domain must contain at least one dot
     /// - TLD must be at least 2 characters
-    fn check_email_pattern(&self, text: &str) -> bool {
+    ///
+    /// `PatternPreset::EmailStrict` dispatches to
+    /// [`Self::check_email_pattern_strict`] instead, which runs a
+    /// genuinely RFC 5322-grade check on the reassembled candidate.
+    fn check_email_pattern(&self, text: &str) -> (bool, Option<MatchInfo>) {
+        if self.config.description.contains("strict") {
+            return self.check_email_pattern_strict(text);
+        }
+
         // Look for @ symbol and . after it
         if let Some(at_pos) = text.find('@') {
             // Must have at least one character before @
             if at_pos == 0 {
-                return false;
+                return (false, None);
             }
-            
+
             let before_at = &text[..at_pos];
             // Local part should have at least one alphanumeric character
             if !before_at.chars().any(|c| c.is_alphanumeric()) {
-                return false;
+                return (false, None);
             }
-            
+
             let after_at = &text[at_pos + 1..];
-            
+
             // Must have a dot after @ with characters before and after the dot
             if let Some(dot_pos) = after_at.find('.') {
                 if dot_pos > 0 && dot_pos + 1 < after_at.len() {
@@ -345,12 +1074,83 @@ impl PatternRule {
                         let tld_chars: String = after_dot.chars()
                             .take_while(|c| c.is_alphanumeric())
                             .collect();
-                        return tld_chars.len() >= 2;
+                        if tld_chars.len() >= 2 {
+                            let end = at_pos + 1 + dot_pos + 1 + tld_chars.len();
+                            // The local part is the `[a-zA-Z0-9._%+-]` run
+                            // directly before the `@`.
+                            let local_start = before_at
+                                .char_indices()
+                                .rev()
+                                .take_while(|&(_, c)| c.is_alphanumeric() || "._%+-".contains(c))
+                                .last()
+                                .map_or(at_pos, |(i, _)| i);
+                            let info = MatchInfo {
+                                rule: self.config.description.clone(),
+                                span: (self.buffer_start + local_start, self.buffer_start + end),
+                                components: MatchComponents::Email {
+                                    local: before_at[local_start..].to_string(),
+                                    domain: text[at_pos + 1..end].to_string(),
+                                },
+                            };
+                            return (true, Some(info));
+                        }
                     }
                 }
             }
         }
-        false
+        (false, None)
+    }
+
+    /// Strict, RFC 5322-grade email validation used by `email_strict`.
+    ///
+    /// Isolates the full `local@domain` candidate span around each `@`
+    /// (rather than the loose "alphanumeric before, a dot after" check above)
+    /// and validates it with [`validate_email_rfc`]. A domain run that still
+    /// reaches the end of the buffered text might keep growing in the next
+    /// chunk, so such a candidate is left unvalidated until something other
+    /// than a domain character terminates it.
+    fn check_email_pattern_strict(&self, text: &str) -> (bool, Option<MatchInfo>) {
+        for (at_byte, _) in text.match_indices('@') {
+            let local_start = text[..at_byte]
+                .char_indices()
+                .rev()
+                .take_while(|&(_, c)| is_local_char(c))
+                .last()
+                .map(|(i, _)| i)
+                .unwrap_or(at_byte);
+
+            let domain_start = at_byte + 1;
+            let mut domain_end = domain_start;
+            for (i, c) in text[domain_start..].char_indices() {
+                if is_domain_char(c) {
+                    domain_end = domain_start + i + c.len_utf8();
+                } else {
+                    break;
+                }
+            }
+
+            if domain_end == text.len() {
+                // Still growing -- wait for more input before judging it.
+                continue;
+            }
+
+            let candidate = &text[local_start..domain_end];
+            if validate_email_rfc(candidate) {
+                let (local, domain) = candidate
+                    .split_once('@')
+                    .expect("candidate spans a '@' by construction");
+                let info = MatchInfo {
+                    rule: self.config.description.clone(),
+                    span: (self.buffer_start + local_start, self.buffer_start + domain_end),
+                    components: MatchComponents::Email {
+                        local: local.to_string(),
+                        domain: domain.to_string(),
+                    },
+                };
+                return (true, Some(info));
+            }
+        }
+        (false, None)
     }
 
     /// Check for IPv4 pattern with octet validation
@@ -394,34 +1194,120 @@ impl PatternRule {
     /// - Between 13-19 digits (covers major card types)
     /// - May be separated by spaces or dashes
     ///
-    /// Note: Does not perform Luhn algorithm validation (checksum)
-    /// for simplicity and performance. Can be added if needed.
-    fn check_credit_card_pattern(&self, text: &str) -> bool {
-        // Look for sequences of digits, possibly separated by spaces or dashes
-        let digits_only: String = text
-            .chars()
-            .filter(|c| c.is_ascii_digit() || *c == ' ' || *c == '-')
-            .collect();
-        
-        let digit_count = digits_only.chars().filter(|c| c.is_ascii_digit()).count();
-        
-        // Credit cards typically have 13-19 digits
-        // 13: Visa (old), 14: Diners Club, 15: Amex, 16: Most common, 19: Maestro
-        if digit_count >= 13 && digit_count <= 19 {
-            // Ensure we have at least 4 consecutive digits somewhere
-            let mut consecutive = 0;
-            for ch in text.chars() {
-                if ch.is_ascii_digit() {
-                    consecutive += 1;
-                    if consecutive >= 4 {
-                        return true;
-                    }
-                } else if ch != ' ' && ch != '-' {
-                    consecutive = 0;
+    /// When `require_checksum` is set, candidates also have to pass the Luhn
+    /// algorithm and not be an all-same-digit placeholder (see
+    /// [`PatternRule::credit_card_strict`]); otherwise any long numeric run
+    /// (an invoice or tracking number, say) matches.
+    ///
+    /// Scans maximal contiguous runs of digit/space/dash characters (rather
+    /// than pooling digits from across the whole buffer) so a match has an
+    /// exact byte span to report in its [`MatchInfo`].
+    fn check_credit_card_pattern(&self, text: &str) -> (bool, Option<MatchInfo>) {
+        let bytes = text.as_bytes();
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if !bytes[i].is_ascii_digit() {
+                i += 1;
+                continue;
+            }
+
+            let start = i;
+            let mut end = i;
+            let mut digits = String::new();
+            while end < bytes.len() && (bytes[end].is_ascii_digit() || bytes[end] == b' ' || bytes[end] == b'-') {
+                if bytes[end].is_ascii_digit() {
+                    digits.push(bytes[end] as char);
+                }
+                end += 1;
+            }
+
+            // Credit cards typically have 13-19 digits
+            // 13: Visa (old), 14: Diners Club, 15: Amex, 16: Most common, 19: Maestro
+            let digit_count = digits.len();
+            if digit_count >= 13 && digit_count <= 19 {
+                // When checksum validation is required, reject candidates
+                // that fail Luhn, or that are an all-same-digit placeholder
+                // (which can pass Luhn by coincidence), so invoice/tracking
+                // numbers and filler digits don't block the stream.
+                let checksum_ok = !self.config.require_checksum
+                    || (!all_same_digit(&digits) && luhn_valid(&digits));
+                if checksum_ok {
+                    let info = MatchInfo {
+                        rule: self.config.description.clone(),
+                        span: (self.buffer_start + start, self.buffer_start + end),
+                        components: MatchComponents::CreditCard {
+                            masked: mask_credit_card(&text[start..end]),
+                        },
+                    };
+                    return (true, Some(info));
                 }
             }
+
+            i = if end > start { end } else { start + 1 };
         }
-        false
+        (false, None)
+    }
+
+    /// Extract the canonical digit string from a credit-card candidate.
+    fn card_digits(text: &str) -> String {
+        text.chars().filter(|c| c.is_ascii_digit()).collect()
+    }
+
+    /// Build the block reason, appending the detected brand for strict cards.
+    fn block_reason(&self, text: &str) -> String {
+        if self.config.require_checksum && self.config.description.contains("credit card") {
+            if let Some(brand) = CardBrand::detect(&Self::card_digits(text)) {
+                return alloc::format!("{} ({})", self.reason, brand.name());
+            }
+        }
+        self.reason.clone()
+    }
+
+    /// Compute the replacement for a single match.
+    ///
+    /// Without a template this is the fixed replacement string. With a
+    /// template, the match's captured sub-spans are substituted into the
+    /// placeholders so the value is partially masked.
+    fn redact(&self, matched: &str, replacement: &str) -> String {
+        let template = match self.template {
+            Some(ref t) => t,
+            None => return replacement.to_string(),
+        };
+
+        let mut out = template.clone();
+        match self.config.description.as_str() {
+            desc if desc.contains("email") => {
+                let (local, domain) = matched
+                    .split_once('@')
+                    .unwrap_or((matched, ""));
+                out = out.replace("$local", local).replace("$domain", domain);
+            }
+            desc if desc.contains("URL") => {
+                let (scheme, rest) = matched
+                    .split_once("://")
+                    .unwrap_or(("", matched));
+                let (host, path) = match rest.find('/') {
+                    Some(pos) => (&rest[..pos], &rest[pos..]),
+                    None => (rest, ""),
+                };
+                out = out
+                    .replace("$scheme", scheme)
+                    .replace("$host", host)
+                    .replace("$path", path);
+            }
+            desc if desc.contains("credit card") => {
+                let digits = Self::card_digits(matched);
+                let last4 = if digits.len() >= 4 {
+                    &digits[digits.len() - 4..]
+                } else {
+                    digits.as_str()
+                };
+                out = out.replace("$last4", last4);
+            }
+            _ => {}
+        }
+        out
     }
 
     /// Perform rewrite by replacing all pattern matches with replacement text
@@ -458,7 +1344,7 @@ impl PatternRule {
                 // End of potential email
                 if in_email && has_at && has_dot_after_at && current.len() > 5 {
                     // Looks like an email - replace it
-                    result.push_str(replacement);
+                    result.push_str(&self.redact(&current, replacement));
                 } else {
                     result.push_str(&current);
                 }
@@ -472,30 +1358,42 @@ impl PatternRule {
         
         // Handle end of string
         if in_email && has_at && has_dot_after_at && current.len() > 5 {
-            result.push_str(replacement);
+            result.push_str(&self.redact(&current, replacement));
         } else {
             result.push_str(&current);
         }
-        
+
         result
     }
 
     fn rewrite_urls(&self, text: &str, replacement: &str) -> String {
-        let mut result = text.to_string();
-        
-        // Find http:// or https://
-        for protocol in &["https://", "http://"] {
-            while let Some(start) = result.find(protocol) {
-                // Find end of URL (next whitespace or end of string)
-                let after_start = &result[start..];
-                let end_offset = after_start.find(|c: char| c.is_whitespace())
-                    .unwrap_or(after_start.len());
-                let url = &result[start..start + end_offset];
-                
-                result = result.replace(url, replacement);
-            }
+        let mut result = String::new();
+        let mut rest = text;
+
+        // Walk forward, masking each URL in place. Scanning from a moving
+        // offset (rather than repeatedly searching the whole string) keeps the
+        // pass terminating even when a template re-emits the scheme.
+        loop {
+            let next = rest
+                .find("https://")
+                .into_iter()
+                .chain(rest.find("http://"))
+                .min();
+            let Some(start) = next else {
+                result.push_str(rest);
+                break;
+            };
+            let after_start = &rest[start..];
+            let end_offset = after_start
+                .find(|c: char| c.is_whitespace())
+                .unwrap_or(after_start.len());
+            let url = &after_start[..end_offset];
+
+            result.push_str(&rest[..start]);
+            result.push_str(&self.redact(url, replacement));
+            rest = &after_start[end_offset..];
         }
-        
+
         result
     }
     fn rewrite_ipv4(&self, text: &str, replacement: &str) -> String {
@@ -508,7 +1406,7 @@ impl PatternRule {
                     !p.is_empty() && p.chars().all(|c| c.is_ascii_digit())
                 });
                 if all_numeric {
-                    result = result.replace(word, replacement);
+                    result = result.replace(word, &self.redact(word, replacement));
                 }
             }
         }
@@ -532,7 +1430,7 @@ impl PatternRule {
             } else {
                 // Check if we accumulated a card number
                 if digit_count >= 13 && digit_count <= 19 {
-                    result.push_str(replacement);
+                    result.push_str(&self.redact(&current, replacement));
                 } else {
                     result.push_str(&current);
                 }
@@ -544,11 +1442,11 @@ impl PatternRule {
         
         // Handle end of string
         if digit_count >= 13 && digit_count <= 19 {
-            result.push_str(replacement);
+            result.push_str(&self.redact(&current, replacement));
         } else {
             result.push_str(&current);
         }
-        
+
         result
     }
 }
@@ -559,45 +1457,121 @@ impl Rule for PatternRule {
             return Decision::Allow;
         }
 
+        // Byte offset of `buffer[0]` *before* this call -- needed below to
+        // translate a match's cumulative-stream span back into an index
+        // within the buffer we're about to consume.
+        let old_buffer_start = self.buffer_start;
+
         // Append chunk to buffer
         self.buffer.push_str(chunk);
+        self.feed_count += 1;
+
+        // Check if buffer matches pattern. The buffer is taken out for the
+        // duration of the check so `matches_pattern` can mutate the rule's
+        // own matcher-slot/scratch state without aliasing `self.buffer`.
+        let buffer = core::mem::take(&mut self.buffer);
+        let matched = self.matches_pattern(&buffer);
 
-        // Check if buffer matches pattern
-        if self.matches_pattern(&self.buffer) {
-            // Save the decision
-            let decision = if let Some(ref replacement) = self.replacement {
-                let rewritten = self.rewrite_text(&self.buffer, replacement);
+        if matched {
+            if let Some(ref replacement) = self.replacement {
+                // Redact mode: only the text up to and including the match
+                // is safe to emit now. Anything after it is withheld in the
+                // buffer in case it starts a fresh match of its own -- see
+                // the non-match branch below and `Self::flush` for why this
+                // matters (without it, a second match starting right after
+                // the first would have already been leaked unredacted).
+                let match_end = self
+                    .last_match_info
+                    .as_ref()
+                    .map(|info| info.span.1.saturating_sub(old_buffer_start))
+                    .filter(|&end| buffer.is_char_boundary(end))
+                    .unwrap_or(buffer.len());
+                let rewritten = self.rewrite_text(&buffer[..match_end], replacement);
+                self.buffer_start += match_end;
+                self.buffer = buffer[match_end..].to_string();
                 Decision::Rewrite {
                     replacement: rewritten,
                 }
             } else {
-                Decision::Block {
-                    reason: self.reason.clone(),
+                let decision = Decision::Block {
+                    reason: self.block_reason(&buffer),
+                };
+                self.buffer_start += buffer.len();
+                decision
+            }
+        } else if self.replacement.is_some() {
+            // Redact mode, no match yet: withhold up to `MAX_BUFFER` trailing
+            // characters, since they could still complete a match next
+            // chunk, and only forward older text once it's old enough to
+            // rule out (returning `Allow` here instead would leak this
+            // chunk's raw text to the caller before we know whether it's
+            // part of a match). `Self::flush` emits whatever is left
+            // withheld once the stream ends and nothing else can complete it.
+            const MAX_BUFFER: usize = 500;
+            let keep = buffer
+                .char_indices()
+                .rev()
+                .nth(MAX_BUFFER - 1)
+                .map_or(0, |(idx, _)| idx);
+            if keep > 0 {
+                let safe = buffer[..keep].to_string();
+                self.buffer_start += keep;
+                self.buffer = buffer[keep..].to_string();
+                Decision::Rewrite { replacement: safe }
+            } else {
+                self.buffer = buffer;
+                Decision::Rewrite {
+                    replacement: String::new(),
                 }
-            };
-            
-            // Clear buffer after match - pattern has been detected and handled
-            self.buffer.clear();
-            decision
+            }
         } else {
             // Keep buffer size reasonable
             // Only keep the last N characters to handle patterns split across chunks
             const MAX_BUFFER: usize = 500;
-            if self.buffer.len() > MAX_BUFFER {
-                let keep = self.buffer.len() - MAX_BUFFER;
-                self.buffer = self.buffer[keep..].to_string();
-            }
+            self.buffer = if buffer.len() > MAX_BUFFER {
+                let keep = buffer
+                    .char_indices()
+                    .rev()
+                    .nth(MAX_BUFFER - 1)
+                    .map_or(0, |(idx, _)| idx);
+                self.buffer_start += keep;
+                buffer[keep..].to_string()
+            } else {
+                buffer
+            };
             Decision::Allow
         }
     }
 
     fn reset(&mut self) {
         self.buffer.clear();
+        self.buffer_start = 0;
+        self.last_match_info = None;
     }
 
     fn name(&self) -> &str {
         "pattern_rule"
     }
+
+    fn last_match_info(&self) -> Option<MatchInfo> {
+        self.last_match_info.clone()
+    }
+
+    /// Emit any text withheld pending a match that straddles the end of the
+    /// stream (see the redact-mode branches in `feed` above), ruling it out
+    /// now that nothing more is coming to complete it.
+    fn flush(&mut self) -> Decision {
+        let tail = core::mem::take(&mut self.buffer);
+        if tail.is_empty() {
+            return Decision::Allow;
+        }
+        self.buffer_start += tail.len();
+        if self.replacement.is_some() {
+            Decision::Rewrite { replacement: tail }
+        } else {
+            Decision::Allow
+        }
+    }
 }
 
 #[cfg(test)]
@@ -634,6 +1608,47 @@ mod tests {
         assert!(rule.feed("user+tag@example.com").is_block());
     }
 
+    #[test]
+    fn test_email_strict_blocks_conformant_address() {
+        let mut rule = PatternRule::email_strict("found email (strict)");
+
+        assert!(rule.feed("Contact: user.name+tag@mail.example.com please").is_block());
+    }
+
+    #[test]
+    fn test_email_strict_rejects_consecutive_dots() {
+        let mut rule = PatternRule::email_strict("found email (strict)");
+
+        assert!(rule.feed("Bad: user..name@example.com here").is_allow());
+    }
+
+    #[test]
+    fn test_email_strict_rejects_hyphen_leading_label() {
+        let mut rule = PatternRule::email_strict("found email (strict)");
+
+        assert!(rule.feed("Bad: user@-example.com here").is_allow());
+    }
+
+    #[test]
+    fn test_very_long_email() {
+        let mut rule = PatternRule::email_strict("found email (strict)");
+        let long_local = "a".repeat(100);
+        let input = alloc::format!("Contact: {}@example.com done", long_local);
+
+        // Over the 64-byte local-part limit -> treated as a non-match.
+        assert!(rule.feed(&input).is_allow());
+    }
+
+    #[test]
+    fn test_email_strict_allows_domain_still_growing_across_chunks() {
+        let mut rule = PatternRule::email_strict("found email (strict)");
+
+        // The domain run reaches the end of the buffer, so it can't yet be
+        // judged -- the rule should neither block nor falsely terminate it.
+        assert!(rule.feed("Contact: user@example").is_allow());
+        assert!(rule.feed(".com now").is_block());
+    }
+
     #[test]
     fn test_no_false_positive_for_non_email() {
         let mut rule = PatternRule::email("found email");
@@ -657,6 +1672,100 @@ mod tests {
         assert!(rule.feed("Check http://test.org/path").is_block());
     }
 
+    #[test]
+    fn test_uri_detection_mailto() {
+        let mut rule = PatternRule::uri("found URI");
+
+        assert!(rule.feed("Contact mailto:user@example.com for details").is_block());
+    }
+
+    #[test]
+    fn test_uri_detection_ftp() {
+        let mut rule = PatternRule::uri("found URI");
+
+        assert!(rule.feed("Files at ftp://files.example.com/data").is_block());
+    }
+
+    #[test]
+    fn test_uri_detection_custom_scheme() {
+        let mut rule = PatternRule::uri("found URI");
+
+        assert!(rule.feed("Open app-action://do-thing").is_block());
+    }
+
+    #[test]
+    fn test_uri_ignores_prose_with_a_colon() {
+        let mut rule = PatternRule::uri("found URI");
+
+        assert!(rule.feed("Note: see below for details").is_allow());
+    }
+
+    #[test]
+    fn test_uri_scheme_split_across_chunks() {
+        let mut rule = PatternRule::uri("found URI");
+
+        assert!(rule.feed("See mail").is_allow());
+        assert!(rule.feed("to:user@example.com now").is_block());
+    }
+
+    #[test]
+    fn test_uri_deny_list_flags_listed_schemes_only() {
+        let mut rule = PatternRule::uri_with_scheme_policy(
+            "embedded link uses a risky scheme",
+            SchemePolicy::Deny(vec!["mailto".to_string(), "ftp".to_string()]),
+        );
+
+        assert!(rule.feed("see ftp://files.example.com/data").is_block());
+
+        rule.reset();
+        assert!(rule.feed("see https://example.com").is_allow());
+    }
+
+    #[test]
+    fn test_uri_allow_list_flags_everything_else() {
+        let mut rule = PatternRule::uri_with_scheme_policy(
+            "embedded link uses an unapproved scheme",
+            SchemePolicy::Allow(vec!["https".to_string()]),
+        );
+
+        assert!(rule.feed("see https://example.com").is_allow());
+
+        rule.reset();
+        assert!(rule.feed("see ftp://files.example.com/data").is_block());
+    }
+
+    #[test]
+    fn test_last_match_info_splits_uri_into_scheme_host_path() {
+        let mut rule = PatternRule::uri("found URI");
+
+        assert!(rule.feed("Files at ftp://files.example.com/data now").is_block());
+        let info = rule.last_match_info().expect("match info");
+        match info.components {
+            MatchComponents::Url { scheme, host, path } => {
+                assert_eq!(scheme, "ftp");
+                assert_eq!(host, "files.example.com");
+                assert_eq!(path, "/data");
+            }
+            other => panic!("expected Url components, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_last_match_info_splits_mailto_with_empty_host() {
+        let mut rule = PatternRule::uri("found URI");
+
+        assert!(rule.feed("Contact mailto:user@example.com now").is_block());
+        let info = rule.last_match_info().expect("match info");
+        match info.components {
+            MatchComponents::Url { scheme, host, path } => {
+                assert_eq!(scheme, "mailto");
+                assert_eq!(host, "");
+                assert_eq!(path, "user@example.com");
+            }
+            other => panic!("expected Url components, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_ipv4_detection() {
         let mut rule = PatternRule::ipv4("found IP address");
@@ -677,6 +1786,35 @@ mod tests {
         assert!(rule.feed("Number: 4532123456789010").is_block());
     }
 
+    #[test]
+    fn test_credit_card_strict_blocks_valid_luhn() {
+        let mut rule = PatternRule::credit_card_strict("found credit card");
+
+        // 4111 1111 1111 1111 is a valid Visa test number.
+        let decision = rule.feed("Card: 4111 1111 1111 1111");
+        assert!(decision.is_block());
+        if let Decision::Block { reason } = decision {
+            assert!(reason.contains("Visa"));
+        }
+    }
+
+    #[test]
+    fn test_credit_card_strict_allows_invalid_luhn() {
+        let mut rule = PatternRule::credit_card_strict("found credit card");
+
+        // Fails the Luhn checksum -> treated as a non-match.
+        assert!(rule.feed("Ref: 4111 1111 1111 1112").is_allow());
+    }
+
+    #[test]
+    fn test_credit_card_strict_rejects_all_same_digit_placeholder() {
+        let mut rule = PatternRule::credit_card_strict("found credit card");
+
+        // All zeros passes Luhn by coincidence (sum of zeros is divisible by
+        // 10) but is never a real card number.
+        assert!(rule.feed("PAN: 0000 0000 0000 0000").is_allow());
+    }
+
     #[test]
     fn test_custom_pattern() {
         let mut rule = PatternRule::custom(
@@ -699,9 +1837,243 @@ mod tests {
         assert!(rule.feed("random text").is_allow());
     }
 
+    #[test]
+    fn test_rewrite_template_keeps_card_last4() {
+        let mut rule = PatternRule::with_rewrite_template(
+            PatternPreset::CreditCard,
+            "**** **** **** $last4",
+        );
+
+        let decision = rule.feed("Card: 4111111111111111");
+        match decision {
+            Decision::Rewrite { replacement } => {
+                assert!(replacement.contains("**** **** **** 1111"));
+            }
+            _ => panic!("expected rewrite"),
+        }
+    }
+
+    #[test]
+    fn test_rewrite_template_keeps_email_domain() {
+        let mut rule = PatternRule::with_rewrite_template(
+            PatternPreset::Email,
+            "***@$domain",
+        );
+
+        let decision = rule.feed("user@example.com ");
+        match decision {
+            Decision::Rewrite { replacement } => {
+                assert!(replacement.contains("***@example.com"));
+            }
+            _ => panic!("expected rewrite"),
+        }
+    }
+
+    #[test]
+    fn test_redact_chains_onto_preset_constructor() {
+        let mut rule = PatternRule::email("found email").redact("[REDACTED]");
+
+        let decision = rule.feed("contact user@example.com today");
+        match decision {
+            Decision::Rewrite { replacement } => {
+                assert!(replacement.contains("[REDACTED]"));
+                assert!(!replacement.contains("user@example.com"));
+            }
+            _ => panic!("expected rewrite"),
+        }
+    }
+
+    #[test]
+    fn test_redact_masked_chains_template_onto_preset_constructor() {
+        let mut rule =
+            PatternRule::from_preset(PatternPreset::CreditCard, "card found").redact_masked("**** **** **** $last4");
+
+        let decision = rule.feed("Card: 4111111111111111");
+        match decision {
+            Decision::Rewrite { replacement } => {
+                assert!(replacement.contains("**** **** **** 1111"));
+            }
+            _ => panic!("expected rewrite"),
+        }
+    }
+
+    #[test]
+    fn test_redact_withholds_partial_match_instead_of_leaking_it() {
+        let mut rule = PatternRule::email("found email").redact("[REDACTED]");
+
+        // The first chunk ends mid-match -- nothing is safe to emit yet, so
+        // it must not come back as `Allow` (which would forward this chunk's
+        // raw, unredacted text to the caller).
+        let decision = rule.feed("contact user@exam");
+        match decision {
+            Decision::Rewrite { replacement } => assert!(replacement.is_empty()),
+            other => panic!("expected an empty withheld rewrite, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_redact_across_chunk_boundary_does_not_duplicate_earlier_text() {
+        let mut rule = PatternRule::email("found email").redact("[REDACTED]");
+
+        let first = rule.feed("contact user@exam");
+        let second = rule.feed("ple.com done");
+
+        let first_text = first.rewritten_text().unwrap_or("").to_string();
+        let second_text = second.rewritten_text().expect("second chunk completes the match");
+
+        // The completing chunk's replacement must not re-emit text already
+        // handed back (redacted or not) by the first chunk.
+        assert!(!second_text.contains(&first_text) || first_text.is_empty());
+        assert!(second_text.contains("[REDACTED]"));
+        assert!(!second_text.contains("user@example.com"));
+        // Trailing text after the match stays withheld for this feed call --
+        // it comes back via `flush`, not bundled into this decision.
+        assert!(!second_text.contains("done"));
+    }
+
+    #[test]
+    fn test_flush_emits_withheld_tail_once_ruled_out() {
+        let mut rule = PatternRule::email("found email").redact("[REDACTED]");
+
+        let completing = rule.feed("contact user@example.com done");
+        assert!(completing.rewritten_text().unwrap().contains("[REDACTED]"));
+
+        // " done" trails the match and was withheld; flushing at end of
+        // stream must hand it back verbatim instead of dropping it.
+        let flushed = rule.flush();
+        assert_eq!(flushed.rewritten_text(), Some(" done"));
+    }
+
+    #[test]
+    fn test_flush_is_a_no_op_without_withheld_text() {
+        let mut rule = PatternRule::email("found email").redact("[REDACTED]");
+        assert!(rule.flush().is_allow());
+    }
+
+    #[test]
+    fn test_flush_default_is_allow_for_block_mode() {
+        let mut rule = PatternRule::email("found email");
+        assert!(rule.feed("user@exam").is_allow());
+        assert!(rule.flush().is_allow());
+    }
+
     #[test]
     fn test_empty_chunk() {
         let mut rule = PatternRule::email("found email");
         assert!(rule.feed("").is_allow());
     }
+
+    #[test]
+    fn test_matcher_slot_resolves_and_survives_idle_within_threshold() {
+        let mut slot = MatcherSlot::new(5);
+        assert_eq!(slot.resolve("email detector", 0), MatcherKind::Email);
+        // Re-resolving well within the idle threshold reuses the cached kind
+        // even if the description were to change underneath it.
+        assert_eq!(slot.resolve("would-be URL now", 3), MatcherKind::Email);
+    }
+
+    #[test]
+    fn test_matcher_slot_discards_after_idle_threshold() {
+        let mut slot = MatcherSlot::new(2);
+        assert_eq!(slot.resolve("email detector", 0), MatcherKind::Email);
+        // Past the idle threshold the slot re-resolves from the description.
+        assert_eq!(slot.resolve("URL detector", 10), MatcherKind::Url);
+    }
+
+    #[test]
+    fn test_with_discard_policy_still_matches() {
+        let mut rule = PatternRule::email("found email").with_discard_policy(DiscardPolicy {
+            max_live: 4,
+            idle_threshold: 1,
+        });
+
+        assert!(rule.feed("user@example.com").is_block());
+    }
+
+    #[test]
+    fn test_pattern_preset_from_str_recognizes_builtin_names() {
+        assert_eq!("email".parse(), Ok(PatternPreset::Email));
+        assert_eq!("email_strict".parse(), Ok(PatternPreset::EmailStrict));
+        assert_eq!("url".parse(), Ok(PatternPreset::Url));
+        assert_eq!("ipv4".parse(), Ok(PatternPreset::Ipv4));
+        assert_eq!("credit_card".parse(), Ok(PatternPreset::CreditCard));
+        assert_eq!(
+            "credit_card_strict".parse(),
+            Ok(PatternPreset::CreditCardStrict)
+        );
+    }
+
+    #[test]
+    fn test_pattern_preset_from_str_rejects_unknown_name() {
+        let result: Result<PatternPreset, UnknownRuleKind> = "phone_number".parse();
+        assert_eq!(result, Err(UnknownRuleKind("phone_number".to_string())));
+    }
+
+    #[test]
+    fn test_last_match_info_splits_email_into_local_and_domain() {
+        let mut rule = PatternRule::email("found email");
+        let input = "Contact: user@example.com";
+
+        assert!(rule.feed(input).is_block());
+        let info = rule.last_match_info().expect("match info");
+        assert_eq!(info.rule, "email address");
+        assert_eq!(info.span, ("Contact: ".len(), input.len()));
+        match info.components {
+            MatchComponents::Email { local, domain } => {
+                assert_eq!(local, "user");
+                assert_eq!(domain, "example.com");
+            }
+            other => panic!("expected Email components, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_last_match_info_splits_url_into_scheme_host_path() {
+        let mut rule = PatternRule::url("found url");
+
+        assert!(rule.feed("Visit https://example.com/a/b now").is_block());
+        let info = rule.last_match_info().expect("match info");
+        match info.components {
+            MatchComponents::Url { scheme, host, path } => {
+                assert_eq!(scheme, "https");
+                assert_eq!(host, "example.com");
+                assert_eq!(path, "/a/b");
+            }
+            other => panic!("expected Url components, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_last_match_info_masks_credit_card_to_last_4() {
+        let mut rule = PatternRule::credit_card("found card");
+
+        assert!(rule.feed("Card: 4532-1234-5678-9010").is_block());
+        let info = rule.last_match_info().expect("match info");
+        match info.components {
+            MatchComponents::CreditCard { masked } => {
+                assert_eq!(masked, "****-****-****-9010");
+            }
+            other => panic!("expected CreditCard components, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_last_match_info_none_without_a_match() {
+        let mut rule = PatternRule::email("found email");
+
+        assert!(rule.feed("no match here").is_allow());
+        assert!(rule.last_match_info().is_none());
+    }
+
+    #[test]
+    fn test_last_match_info_span_is_cumulative_across_chunks() {
+        let mut rule = PatternRule::email("found email");
+
+        assert!(rule.feed("prefix ").is_allow());
+        assert!(rule.feed("user@example.com").is_block());
+        let info = rule.last_match_info().expect("match info");
+        // The span is measured from the start of the cumulative stream, so it
+        // begins after "prefix " rather than at 0 of the final `feed` call.
+        assert_eq!(info.span, ("prefix ".len(), "prefix user@example.com".len()));
+    }
 }
@@ -0,0 +1,301 @@
+//! External filter rule delegating decisions to an out-of-process helper
+//!
+//! [`ProcessRule`] is the milter-integration shape applied to [`Rule`]: each
+//! `feed` streams the chunk to a long-lived subprocess over a small
+//! length-prefixed framing protocol on its stdin/stdout and maps the reply
+//! back onto [`Decision`]. The subprocess is spawned once and kept alive
+//! across `feed` calls (not re-spawned per chunk) so a helper written in
+//! another language, or backed by a model this crate can't embed, can keep
+//! its own streaming state the same way the in-process rules do.
+//!
+//! # Wire protocol
+//!
+//! Every frame, in both directions, is a 4-byte big-endian length prefix
+//! followed by that many bytes of payload. In frames sent to the helper the
+//! first payload byte is a frame kind; the helper's reply frame has no kind
+//! byte, its whole payload is the reply text:
+//!
+//! - `0x00` (feed): payload is the chunk's UTF-8 bytes. The helper must
+//!   reply with exactly one frame whose payload is one of `allow`,
+//!   `block:<reason>`, or `rewrite:<replacement>` (ASCII prefix, UTF-8
+//!   remainder).
+//! - `0x01` (reset): payload is empty, sent on [`Rule::reset`]. No reply is
+//!   expected -- this tells the helper to drop whatever streaming state it
+//!   keeps for this connection, mirroring what every in-process rule does
+//!   in its own `reset()`.
+//!
+//! # Failure handling
+//!
+//! If the helper crashes, closes its pipes, or sends a reply this module
+//! doesn't recognize, the connection is dropped and [`FailurePolicy`]
+//! decides the chunk's decision (a fresh subprocess is spawned lazily on
+//! the next `feed`) -- the same fail-open/fail-closed trade-off
+//! [`crate::worker::TimeoutPolicy`] offers for a slow async rule, applied
+//! here to a dead one instead.
+//!
+//! This module requires the `process` feature (which implies `std` --
+//! spawning a subprocess isn't available otherwise).
+
+use std::io::{Read, Write};
+use std::process::{Child, Command, Stdio};
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::core::{Decision, Rule};
+
+const FRAME_FEED: u8 = 0x00;
+const FRAME_RESET: u8 = 0x01;
+
+/// What decision to apply when the external helper can't be reached or
+/// sends a reply [`ProcessRule`] doesn't understand.
+///
+/// Mirrors the fail-open / fail-closed trade-off of
+/// [`crate::worker::TimeoutPolicy`]: prefer availability, or prefer safety.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FailurePolicy {
+    /// Fail open: a dead or misbehaving helper is treated as `Allow`.
+    FailOpen,
+    /// Fail closed: a dead or misbehaving helper blocks with the given reason.
+    FailClosed(String),
+}
+
+impl Default for FailurePolicy {
+    fn default() -> Self {
+        FailurePolicy::FailOpen
+    }
+}
+
+/// A rule that delegates its decision to an external program, analogous to
+/// a milter integration. See the module docs for the wire protocol.
+///
+/// The subprocess is spawned lazily on the first `feed` and kept alive
+/// across calls; a dead connection is dropped and respawned on the next
+/// `feed` rather than failing permanently.
+pub struct ProcessRule {
+    program: String,
+    args: Vec<String>,
+    name: String,
+    failure_policy: FailurePolicy,
+    child: Option<Child>,
+}
+
+impl ProcessRule {
+    /// Delegate to `program args...`, labeled `name` for
+    /// [`Rule::name`]/diagnostics. Defaults to [`FailurePolicy::FailOpen`];
+    /// use [`Self::fail_closed`] to change that.
+    pub fn new(program: &str, args: Vec<String>, name: &str) -> Self {
+        Self {
+            program: program.to_string(),
+            args,
+            name: name.to_string(),
+            failure_policy: FailurePolicy::default(),
+            child: None,
+        }
+    }
+
+    /// Fail closed with `reason` instead of allowing when the helper is
+    /// unreachable or misbehaves.
+    pub fn fail_closed(mut self, reason: &str) -> Self {
+        self.failure_policy = FailurePolicy::FailClosed(reason.to_string());
+        self
+    }
+
+    /// Spawn the helper if there isn't a live one already.
+    fn ensure_spawned(&mut self) -> std::io::Result<()> {
+        if self.child.is_some() {
+            return Ok(());
+        }
+        let child = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        self.child = Some(child);
+        Ok(())
+    }
+
+    /// Send `chunk` as a feed frame and read back the helper's decision.
+    fn exchange(&mut self, chunk: &str) -> std::io::Result<Decision> {
+        self.ensure_spawned()?;
+        let child = self.child.as_mut().expect("just ensured spawned");
+
+        let stdin = child
+            .stdin
+            .as_mut()
+            .ok_or_else(|| std::io::Error::other("helper stdin unavailable"))?;
+        write_frame(stdin, FRAME_FEED, chunk.as_bytes())?;
+
+        let stdout = child
+            .stdout
+            .as_mut()
+            .ok_or_else(|| std::io::Error::other("helper stdout unavailable"))?;
+        let payload = read_frame(stdout)?;
+        let reply = String::from_utf8(payload)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        parse_reply(&reply)
+    }
+
+    /// The decision [`Self::failure_policy`] dictates for an unreachable or
+    /// misbehaving helper.
+    fn failure_decision(&self) -> Decision {
+        match &self.failure_policy {
+            FailurePolicy::FailOpen => Decision::Allow,
+            FailurePolicy::FailClosed(reason) => Decision::Block {
+                reason: reason.clone(),
+            },
+        }
+    }
+}
+
+impl Rule for ProcessRule {
+    fn feed(&mut self, chunk: &str) -> Decision {
+        match self.exchange(chunk) {
+            Ok(decision) => decision,
+            Err(_) => {
+                // Drop the connection so the next `feed` respawns a fresh
+                // helper instead of repeatedly hitting the same dead pipe.
+                if let Some(mut child) = self.child.take() {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                }
+                self.failure_decision()
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        if let Some(child) = self.child.as_mut() {
+            if let Some(stdin) = child.stdin.as_mut() {
+                // Best-effort: if the helper is already gone, the next
+                // `feed` fails, drops it and applies the failure policy, and
+                // the `feed` after that respawns it.
+                let _ = write_frame(stdin, FRAME_RESET, b"");
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Drop for ProcessRule {
+    fn drop(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+fn write_frame(out: &mut impl Write, kind: u8, payload: &[u8]) -> std::io::Result<()> {
+    let len = (1 + payload.len()) as u32;
+    out.write_all(&len.to_be_bytes())?;
+    out.write_all(&[kind])?;
+    out.write_all(payload)?;
+    out.flush()
+}
+
+fn read_frame(input: &mut impl Read) -> std::io::Result<Vec<u8>> {
+    let mut len_buf = [0u8; 4];
+    input.read_exact(&mut len_buf)?;
+    let len = u32::from_be_bytes(len_buf) as usize;
+    let mut payload = vec![0u8; len];
+    input.read_exact(&mut payload)?;
+    Ok(payload)
+}
+
+fn parse_reply(reply: &str) -> std::io::Result<Decision> {
+    if reply == "allow" {
+        Ok(Decision::Allow)
+    } else if let Some(reason) = reply.strip_prefix("block:") {
+        Ok(Decision::Block {
+            reason: reason.to_string(),
+        })
+    } else if let Some(replacement) = reply.strip_prefix("rewrite:") {
+        Ok(Decision::Rewrite {
+            replacement: replacement.to_string(),
+        })
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            alloc::format!("unrecognized helper reply: {reply:?}"),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reply_allow() {
+        assert_eq!(parse_reply("allow").unwrap(), Decision::Allow);
+    }
+
+    #[test]
+    fn test_parse_reply_block_carries_reason() {
+        let decision = parse_reply("block:phishing link").unwrap();
+        assert_eq!(
+            decision,
+            Decision::Block {
+                reason: "phishing link".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_reply_rewrite_carries_replacement() {
+        let decision = parse_reply("rewrite:redacted text").unwrap();
+        assert_eq!(
+            decision,
+            Decision::Rewrite {
+                replacement: "redacted text".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_reply_unrecognized_is_an_error() {
+        assert!(parse_reply("maybe?").is_err());
+    }
+
+    #[test]
+    fn test_frame_round_trips_through_a_byte_buffer() {
+        let mut buf = Vec::new();
+        write_frame(&mut buf, FRAME_FEED, b"hello").unwrap();
+        let mut cursor = &buf[..];
+        let payload = read_frame(&mut cursor).unwrap();
+        assert_eq!(payload, [&[FRAME_FEED][..], b"hello"].concat());
+    }
+
+    #[test]
+    fn test_unreachable_program_fails_open_by_default() {
+        let mut rule = ProcessRule::new(
+            "/nonexistent/streamguard-helper-binary",
+            Vec::new(),
+            "external",
+        );
+        assert!(rule.feed("anything").is_allow());
+    }
+
+    #[test]
+    fn test_unreachable_program_fails_closed_when_configured() {
+        let mut rule = ProcessRule::new(
+            "/nonexistent/streamguard-helper-binary",
+            Vec::new(),
+            "external",
+        )
+        .fail_closed("external filter unreachable");
+        let decision = rule.feed("anything");
+        assert_eq!(
+            decision,
+            Decision::Block {
+                reason: "external filter unreachable".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,431 @@
+//! Regex-backed custom detection rules
+//!
+//! The module doc on [`crate::rules::pattern`] is explicit that the built-in
+//! presets (email, URL, IPv4, credit card) intentionally avoid a full regex
+//! engine -- there are only a handful of shapes to hand-code, and doing so
+//! keeps that module dependency-free and easy to audit. User-defined
+//! detectors don't share that tradeoff: API keys, JWTs, SSNs and the like
+//! come in too many shapes to hand-code a matcher per pattern, and
+//! `PatternRule::custom` only ever does literal keyword matching.
+//!
+//! `RegexRule` can also rewrite instead of block -- see
+//! [`RegexRuleBuilder::rewrite`] / [`RegexRule::regex_rewrite`] -- expanding
+//! a capture-group-aware template (`$1`, `${name}`, ...) in place of the
+//! match. This is the regex counterpart to
+//! [`PatternRule::redact`](crate::rules::PatternRule::redact): the built-in
+//! presets stay hand-coded and template-driven for the reasons above, while
+//! user-defined patterns get the same redaction ergonomics through the
+//! `regex` crate's own capture-expansion, without PatternRule growing a
+//! regex dependency of its own.
+//!
+//! This module fills that gap with [`RegexRule`], a rule built on the
+//! `regex` crate instead. It's gated behind the optional `regex` feature
+//! rather than folded into [`crate::rules::pattern`], so the zero-dependency
+//! guarantee for the built-in presets still holds for anyone who doesn't
+//! enable it. The dependency itself still fits the crate's "DFA-based, no
+//! backtracking" principle from the top-level docs: `regex` compiles to a
+//! finite automaton and guarantees linear-time matching with no
+//! backtracking, the same property the hand-coded matchers give up a little
+//! convenience to get by hand.
+//!
+//! # Streaming
+//!
+//! A compiled regex has no notion of "this match might still be growing" --
+//! it only ever matches or doesn't over whatever text it's handed. So
+//! instead of re-scanning an ever-growing buffer the way [`PatternRule`]
+//! does, this rule keeps a bounded *tail* of the most recently fed text
+//! (capped at [`RegexRuleBuilder::max_match_len`]), appends each new chunk to
+//! it, and re-runs the regex over `tail + chunk`. Anything matched is
+//! therefore found within text actually searched; the cap also bounds how
+//! far back a match can reach across a chunk boundary, trading recall on
+//! patterns wider than the cap for a hard bound on memory.
+//!
+//! # Memory
+//!
+//! The tail is truncated to at most `max_match_len` bytes after every
+//! `feed` that doesn't match, regardless of what the pattern itself would
+//! otherwise allow -- so a pattern like `.*` cannot make the rule buffer the
+//! whole stream. Raise the cap for patterns that legitimately need to match
+//! across a wider span; the default ([`DEFAULT_MAX_MATCH_LEN`]) covers
+//! typical single-token secrets (API keys, JWTs, SSNs) with room to spare.
+//!
+//! [`PatternRule`]: crate::rules::PatternRule
+
+use alloc::string::{String, ToString};
+
+use regex::{Regex, RegexBuilder};
+
+use crate::core::{Decision, MatchComponents, MatchInfo, Rule};
+
+/// Default cap, in bytes, on the tail buffer [`RegexRule`] retains across
+/// `feed` calls to catch matches straddling a chunk boundary.
+pub const DEFAULT_MAX_MATCH_LEN: usize = 256;
+
+/// A pattern failed to compile into a regex.
+///
+/// Wraps the message from the underlying `regex` crate so callers of this
+/// crate's public API never need to depend on `regex` themselves just to
+/// name the error type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegexCompileError(pub String);
+
+/// Builder for [`RegexRule`], mirroring the flags `regex::RegexBuilder`
+/// itself exposes.
+pub struct RegexRuleBuilder {
+    pattern: String,
+    reason: String,
+    label: String,
+    case_insensitive: bool,
+    dot_matches_new_line: bool,
+    unicode: bool,
+    max_match_len: usize,
+    template: Option<String>,
+}
+
+impl RegexRuleBuilder {
+    /// Start building a rule that blocks on the first match of `pattern`.
+    ///
+    /// `reason` is the human-readable block reason; `label` identifies the
+    /// rule in [`Rule::last_match_info`].
+    pub fn new(pattern: &str, reason: &str, label: &str) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            reason: reason.to_string(),
+            label: label.to_string(),
+            case_insensitive: false,
+            dot_matches_new_line: false,
+            unicode: true,
+            max_match_len: DEFAULT_MAX_MATCH_LEN,
+            template: None,
+        }
+    }
+
+    /// Match regardless of case.
+    pub fn case_insensitive(mut self, enabled: bool) -> Self {
+        self.case_insensitive = enabled;
+        self
+    }
+
+    /// Let `.` match `\n` as well as every other character.
+    pub fn dot_matches_new_line(mut self, enabled: bool) -> Self {
+        self.dot_matches_new_line = enabled;
+        self
+    }
+
+    /// Enable Unicode-aware character classes (`\w`, `\s`, ...) and case
+    /// folding. Enabled by default; disable for byte-oriented patterns.
+    pub fn unicode(mut self, enabled: bool) -> Self {
+        self.unicode = enabled;
+        self
+    }
+
+    /// Rewrite instead of blocking: replace the match with `template`,
+    /// expanded the same way `regex::Captures::expand` does, so it may
+    /// reference capture groups as `$1`, `$2`, ... or `${name}` for named
+    /// groups. This preserves structure instead of wholesale redaction --
+    /// e.g. pattern `([^@]+)@(.+)` with template `[USER]@$2` keeps the
+    /// domain, or `(\d{4})$` on a credit-card pattern with template
+    /// `****-****-****-$1` keeps the last four digits.
+    ///
+    /// `reason` (from [`Self::new`]) is unused once this is set, since a
+    /// rewriting rule never blocks.
+    pub fn rewrite(mut self, template: &str) -> Self {
+        self.template = Some(template.to_string());
+        self
+    }
+
+    /// Cap, in bytes, on the tail buffer retained across `feed` calls.
+    ///
+    /// Bounds worst-case memory for patterns (e.g. `.*`) that would
+    /// otherwise match an unbounded amount of buffered text: regardless of
+    /// what the pattern allows, the rule only ever searches at most
+    /// `max_match_len` bytes of carried-over tail plus one chunk at a time.
+    pub fn max_match_len(mut self, len: usize) -> Self {
+        self.max_match_len = len;
+        self
+    }
+
+    /// Compile the pattern and build the rule.
+    pub fn build(self) -> Result<RegexRule, RegexCompileError> {
+        let regex = RegexBuilder::new(&self.pattern)
+            .case_insensitive(self.case_insensitive)
+            .dot_matches_new_line(self.dot_matches_new_line)
+            .unicode(self.unicode)
+            .build()
+            .map_err(|err| RegexCompileError(err.to_string()))?;
+        Ok(RegexRule {
+            regex,
+            reason: self.reason,
+            label: self.label,
+            max_match_len: self.max_match_len,
+            template: self.template,
+            tail: String::new(),
+            consumed: 0,
+            last_match_info: None,
+        })
+    }
+}
+
+/// A rule that blocks on the first match of a user-supplied regex.
+///
+/// See the module docs for the streaming strategy and memory bound. Build
+/// one with [`RegexRule::new`] for the defaults, or [`RegexRuleBuilder`] for
+/// case-insensitivity, `.`-matches-newline, Unicode mode, or a non-default
+/// match-length cap.
+///
+/// ```rust
+/// # #[cfg(feature = "regex")]
+/// # fn example() -> Result<(), streamguard::rules::RegexCompileError> {
+/// use streamguard::rules::RegexRule;
+/// use streamguard::Rule;
+///
+/// let mut rule = RegexRule::new(r"sk-[A-Za-z0-9]{20,}", "found API key", "api_key")?;
+/// assert!(rule.feed("key: sk-abcdefghijklmnopqrstuvwxyz").is_block());
+/// # Ok(())
+/// # }
+/// ```
+pub struct RegexRule {
+    regex: Regex,
+    reason: String,
+    label: String,
+    max_match_len: usize,
+    /// Capture-group-aware replacement template; see
+    /// [`RegexRuleBuilder::rewrite`]. `None` means this rule blocks instead.
+    template: Option<String>,
+    /// Bounded carry-over from the previous `feed`, re-searched with each
+    /// new chunk so a match straddling the boundary is still found.
+    tail: String,
+    /// Cumulative bytes fed so far, used to report [`Self::last_match_info`]
+    /// spans relative to the whole stream rather than just the tail.
+    consumed: usize,
+    last_match_info: Option<MatchInfo>,
+}
+
+impl RegexRule {
+    /// Create a rule with the default flags and match-length cap. Use
+    /// [`RegexRuleBuilder`] to customize either.
+    pub fn new(pattern: &str, reason: &str, label: &str) -> Result<Self, RegexCompileError> {
+        RegexRuleBuilder::new(pattern, reason, label).build()
+    }
+
+    /// Create a rule that rewrites matches of `pattern` using `template`
+    /// instead of blocking. See [`RegexRuleBuilder::rewrite`] for the
+    /// template syntax; use [`RegexRuleBuilder`] directly for case
+    /// insensitivity or a non-default match-length cap alongside rewriting.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "regex")]
+    /// # fn example() -> Result<(), streamguard::rules::RegexCompileError> {
+    /// use streamguard::rules::RegexRule;
+    /// use streamguard::Rule;
+    ///
+    /// let mut rule = RegexRule::regex_rewrite(r"([^@\s]+)@([^\s]+)", "[USER]@$2", "email")?;
+    /// let decision = rule.feed("contact john@example.com today");
+    /// assert_eq!(
+    ///     decision.rewritten_text(),
+    ///     Some("contact [USER]@example.com today")
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn regex_rewrite(
+        pattern: &str,
+        template: &str,
+        label: &str,
+    ) -> Result<Self, RegexCompileError> {
+        RegexRuleBuilder::new(pattern, "", label)
+            .rewrite(template)
+            .build()
+    }
+}
+
+/// Return the nearest char boundary in `s` that keeps at most `max_len`
+/// trailing bytes, so truncating there never splits a UTF-8 sequence.
+fn tail_start_index(s: &str, max_len: usize) -> usize {
+    if s.len() <= max_len {
+        return 0;
+    }
+    let mut idx = s.len() - max_len;
+    while idx < s.len() && !s.is_char_boundary(idx) {
+        idx += 1;
+    }
+    idx
+}
+
+impl Rule for RegexRule {
+    fn feed(&mut self, chunk: &str) -> Decision {
+        self.last_match_info = None;
+        if chunk.is_empty() {
+            return Decision::Allow;
+        }
+
+        let window_start = self.consumed - self.tail.len();
+        let mut combined = core::mem::take(&mut self.tail);
+        combined.push_str(chunk);
+        self.consumed += chunk.len();
+
+        if let Some(caps) = self.regex.captures(&combined) {
+            let whole = caps.get(0).expect("capture group 0 always matches");
+            self.last_match_info = Some(MatchInfo {
+                rule: self.label.clone(),
+                span: (window_start + whole.start(), window_start + whole.end()),
+                components: MatchComponents::None,
+            });
+
+            if let Some(template) = &self.template {
+                let mut expanded = String::new();
+                caps.expand(template, &mut expanded);
+                let mut rewritten = String::with_capacity(combined.len());
+                rewritten.push_str(&combined[..whole.start()]);
+                rewritten.push_str(&expanded);
+                rewritten.push_str(&combined[whole.end()..]);
+
+                let keep_from = tail_start_index(&rewritten, self.max_match_len);
+                self.tail = rewritten[keep_from..].to_string();
+                return Decision::Rewrite {
+                    replacement: rewritten,
+                };
+            }
+
+            return Decision::Block {
+                reason: self.reason.clone(),
+            };
+        }
+
+        let keep_from = tail_start_index(&combined, self.max_match_len);
+        self.tail = combined[keep_from..].to_string();
+        Decision::Allow
+    }
+
+    fn reset(&mut self) {
+        self.tail.clear();
+        self.consumed = 0;
+        self.last_match_info = None;
+    }
+
+    fn name(&self) -> &str {
+        "regex_rule"
+    }
+
+    fn last_match_info(&self) -> Option<MatchInfo> {
+        self.last_match_info.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regex_rule_blocks_on_match() {
+        let mut rule = RegexRule::new(r"sk-[A-Za-z0-9]{20,}", "found API key", "api_key").unwrap();
+        assert!(rule
+            .feed("key: sk-abcdefghijklmnopqrstuvwxyz")
+            .is_block());
+    }
+
+    #[test]
+    fn test_regex_rule_allows_non_matching_text() {
+        let mut rule = RegexRule::new(r"sk-[A-Za-z0-9]{20,}", "found API key", "api_key").unwrap();
+        assert!(rule.feed("just some ordinary text").is_allow());
+    }
+
+    #[test]
+    fn test_regex_rule_match_across_chunk_boundary() {
+        let mut rule = RegexRule::new(r"sk-[A-Za-z0-9]{20,}", "found API key", "api_key").unwrap();
+        assert!(rule.feed("key: sk-abcdefghijklmno").is_allow());
+        assert!(rule.feed("pqrstuvwxyz more text").is_block());
+    }
+
+    #[test]
+    fn test_regex_rule_case_insensitive() {
+        let mut rule = RegexRuleBuilder::new("secret", "found secret", "secret")
+            .case_insensitive(true)
+            .build()
+            .unwrap();
+        assert!(rule.feed("the SECRET is out").is_block());
+    }
+
+    #[test]
+    fn test_regex_rule_reports_cumulative_span() {
+        let mut rule = RegexRule::new(r"sk-[A-Za-z0-9]{6}", "found API key", "api_key").unwrap();
+        assert!(rule.feed("prefix ").is_allow());
+        assert!(rule.feed("sk-abc123").is_block());
+        let info = rule.last_match_info().unwrap();
+        assert_eq!(info.span, (7, 16));
+    }
+
+    #[test]
+    fn test_regex_rule_cap_bounds_worst_case_memory() {
+        // A run of 10 `a`s straddles this boundary (5 trailing the first
+        // chunk, 5 leading the second), but the cap only carries over the
+        // last 4 bytes of tail -- so one `a` is dropped and the run is never
+        // reassembled. This is the tradeoff documented on
+        // `RegexRuleBuilder::max_match_len`: the cap bounds memory for
+        // patterns like `.*` at the cost of missing matches wider than it.
+        let mut rule = RegexRuleBuilder::new("a{10}", "found run", "run")
+            .max_match_len(4)
+            .build()
+            .unwrap();
+        let mut first_chunk = "b".repeat(20);
+        first_chunk.push_str(&"a".repeat(5));
+        assert!(rule.feed(&first_chunk).is_allow());
+        assert!(rule.feed(&"a".repeat(5)).is_allow());
+    }
+
+    #[test]
+    fn test_regex_compile_error_on_bad_pattern() {
+        let result = RegexRule::new("(unclosed", "n/a", "bad");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_regex_rewrite_keeps_captured_group() {
+        let mut rule =
+            RegexRule::regex_rewrite(r"([^@\s]+)@([^\s]+)", "[USER]@$2", "email").unwrap();
+        let decision = rule.feed("contact john@example.com today");
+        assert_eq!(
+            decision.rewritten_text(),
+            Some("contact [USER]@example.com today")
+        );
+    }
+
+    #[test]
+    fn test_regex_rewrite_masks_all_but_last_four_digits() {
+        let mut rule = RegexRule::regex_rewrite(
+            r"\d{4}[- ]?\d{4}[- ]?\d{4}[- ]?(\d{4})",
+            "****-****-****-$1",
+            "credit_card",
+        )
+        .unwrap();
+        let decision = rule.feed("card 4111 1111 1111 1111 on file");
+        assert_eq!(
+            decision.rewritten_text(),
+            Some("card ****-****-****-1111 on file")
+        );
+    }
+
+    #[test]
+    fn test_regex_rewrite_match_across_chunk_boundary() {
+        let mut rule = RegexRuleBuilder::new(r"sk-([A-Za-z0-9]{20,})", "n/a", "api_key")
+            .rewrite("sk-$1-redacted")
+            .build()
+            .unwrap();
+        assert!(rule.feed("key: sk-abcdefghijklmno").is_allow());
+        let decision = rule.feed("pqrstuvwxyz more text");
+        assert_eq!(
+            decision.rewritten_text(),
+            Some("key: sk-abcdefghijklmnopqrstuvwxyz-redacted more text")
+        );
+    }
+
+    #[test]
+    fn test_regex_rewrite_reports_match_span() {
+        let mut rule = RegexRule::regex_rewrite(r"sk-[A-Za-z0-9]{6}", "[REDACTED]", "api_key")
+            .unwrap();
+        assert!(rule.feed("prefix ").is_allow());
+        assert!(rule.feed("sk-abc123 suffix").is_rewrite());
+        let info = rule.last_match_info().unwrap();
+        assert_eq!(info.span, (7, 16));
+    }
+}
@@ -0,0 +1,420 @@
+//! Declarative, named rule packs loaded from JSON/TOML
+//!
+//! A [`RulePack`] is the adblock-filter-list idea (see
+//! [`crate::rules::filterlist`]) applied to [`PatternRule`] and
+//! [`ForbiddenSequenceRule`], but as structured data instead of a
+//! line-oriented text format: each entry carries a `name` and optional
+//! `aliases` so a caller can ship and version a guard policy as a file and
+//! later look rules up by name for enable/disable tooling, rather than only
+//! ever appending rules imperatively via [`GuardEngine::add_rule`].
+//!
+//! Unlike [`crate::config::EngineConfig`], a pack describes only rules, not
+//! engine-wide settings (`score_threshold`, `score_decay`, rewrite mode) --
+//! it's meant to be the portable, composable policy unit, loaded alongside
+//! whatever engine-level config already exists.
+//!
+//! For the common case of building a whole engine straight from a pack
+//! string or file, see
+//! [`GuardEngine::from_config_str`](crate::GuardEngine::from_config_str) /
+//! [`GuardEngine::from_config_file`](crate::GuardEngine::from_config_file).
+//!
+//! [`GuardEngine::add_rule`]: crate::GuardEngine::add_rule
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::Rule;
+use crate::rules::{ForbiddenSequenceRule, PatternPreset, PatternRule, SequenceConfig, UnknownRuleKind};
+
+/// The kind of rule a [`RulePackEntry`] builds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RulePackKind {
+    /// A [`PatternRule`], built from `preset` or `pattern`.
+    Pattern,
+    /// A [`ForbiddenSequenceRule`], built from `tokens`.
+    Sequence,
+}
+
+/// One rule in a [`RulePack`].
+///
+/// Which fields apply depends on `kind`: a `Pattern` entry needs exactly one
+/// of `preset` (a registered [`PatternPreset`] name) or `pattern` (a custom
+/// regex, requiring the optional `regex` feature -- see
+/// [`crate::rules::RegexRule`]), plus an optional `action` (`"block"`, the
+/// default, or `"rewrite"` with a `placeholder`) when built from `preset`;
+/// a `Sequence` entry needs a non-empty `tokens` list, with
+/// `strict`/`gaps`/`stop_words` mirroring [`SequenceConfig`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RulePackEntry {
+    /// Unique name this rule can be looked up by.
+    pub name: String,
+    /// Additional names this rule can be looked up by.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Which rule type this entry builds.
+    pub kind: RulePackKind,
+    /// Human-readable reason the rule blocks with.
+    pub message: String,
+    /// `Pattern` kind: a registered preset name (`"email"`, `"url"`, ...).
+    #[serde(default)]
+    pub preset: Option<String>,
+    /// `Pattern` kind: a custom regex, built via [`crate::rules::RegexRule`].
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// `Pattern` kind: `"block"` (the default) or `"rewrite"`. Ignored when
+    /// `pattern` (rather than `preset`) is set -- custom regex patterns only
+    /// block, see [`build_regex_pattern`].
+    #[serde(default)]
+    pub action: Option<String>,
+    /// `Pattern` kind with `action: "rewrite"`: the replacement text,
+    /// via [`crate::rules::PatternRule::redact`].
+    #[serde(default)]
+    pub placeholder: Option<String>,
+    /// `Sequence` kind: the token sequence to detect.
+    #[serde(default)]
+    pub tokens: Vec<String>,
+    /// `Sequence` kind: disallow gaps between tokens (see
+    /// [`SequenceConfig::strict`]). Overridden by `gaps` if both are set.
+    #[serde(default)]
+    pub strict: bool,
+    /// `Sequence` kind: explicitly set whether gaps are allowed, taking
+    /// precedence over `strict`.
+    #[serde(default)]
+    pub gaps: Option<bool>,
+    /// `Sequence` kind: words that reset the sequence when encountered.
+    #[serde(default)]
+    pub stop_words: Vec<String>,
+}
+
+/// A pack of named rules, parsed from JSON or TOML via [`Self::from_json`]
+/// / [`Self::from_toml`] and loaded with
+/// [`GuardEngine::load_rule_pack`](crate::GuardEngine::load_rule_pack).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RulePack {
+    /// The rules to build, in order.
+    #[serde(default)]
+    pub rules: Vec<RulePackEntry>,
+}
+
+impl RulePack {
+    /// Parse a rule pack from a JSON string.
+    pub fn from_json(s: &str) -> Result<Self, RulePackError> {
+        serde_json::from_str(s).map_err(|e| RulePackError::Parse(e.to_string()))
+    }
+
+    /// Parse a rule pack from a TOML string.
+    pub fn from_toml(s: &str) -> Result<Self, RulePackError> {
+        toml::from_str(s).map_err(|e| RulePackError::Parse(e.to_string()))
+    }
+}
+
+/// Errors produced while parsing or building a [`RulePack`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RulePackError {
+    /// The pack text could not be parsed.
+    Parse(String),
+    /// A `Pattern` entry's `preset` name isn't a registered [`PatternPreset`].
+    UnknownPreset(String),
+    /// A required field for an entry's `kind` was missing, named `(entry, field)`.
+    MissingPayload {
+        /// Name of the entry that was being built.
+        name: String,
+        /// The field that was required.
+        field: String,
+    },
+    /// A `Pattern` entry's custom `pattern` failed to compile as a regex,
+    /// named `(entry, message)`.
+    InvalidPattern(String, String),
+    /// A `Pattern` entry set `pattern`, but this build lacks the `regex`
+    /// feature required to compile custom regexes.
+    RegexFeatureRequired(String),
+    /// A `Pattern` entry's `action` wasn't `"block"` or `"rewrite"`, named
+    /// `(entry, action)`.
+    UnknownAction(String, String),
+    /// A config file couldn't be read from disk (`std` feature only -- see
+    /// [`crate::GuardEngine::from_config_file`]).
+    #[cfg(feature = "std")]
+    Io(String),
+}
+
+/// A rule built from a [`RulePackEntry`], paired with the name/aliases it
+/// should be reachable by.
+pub struct NamedRule {
+    /// The entry's `name`.
+    pub name: String,
+    /// The entry's `aliases`.
+    pub aliases: Vec<String>,
+    /// The constructed rule.
+    pub rule: Box<dyn Rule>,
+}
+
+/// Build every entry in `pack` into a [`NamedRule`], in order.
+///
+/// Fails on the first entry that can't be built rather than skipping it --
+/// unlike [`crate::rules::filterlist`]'s tolerant text format, a rule pack is
+/// structured data and a malformed entry is a config bug worth surfacing.
+pub fn build(pack: &RulePack) -> Result<Vec<NamedRule>, RulePackError> {
+    pack.rules
+        .iter()
+        .map(|entry| {
+            let rule = match entry.kind {
+                RulePackKind::Pattern => build_pattern(entry)?,
+                RulePackKind::Sequence => build_sequence(entry)?,
+            };
+            Ok(NamedRule {
+                name: entry.name.clone(),
+                aliases: entry.aliases.clone(),
+                rule,
+            })
+        })
+        .collect()
+}
+
+/// Build a `Pattern`-kind entry from its `preset` or `pattern` field.
+fn build_pattern(entry: &RulePackEntry) -> Result<Box<dyn Rule>, RulePackError> {
+    if let Some(preset_name) = &entry.preset {
+        let preset = PatternPreset::from_str(preset_name)
+            .map_err(|UnknownRuleKind(name)| RulePackError::UnknownPreset(name))?;
+        let rule = PatternRule::from_preset(preset, &entry.message);
+        return match entry.action.as_deref() {
+            None | Some("block") => Ok(Box::new(rule)),
+            Some("rewrite") => {
+                let placeholder = entry.placeholder.as_deref().ok_or_else(|| RulePackError::MissingPayload {
+                    name: entry.name.clone(),
+                    field: "placeholder".to_string(),
+                })?;
+                Ok(Box::new(rule.redact(placeholder)))
+            }
+            Some(other) => Err(RulePackError::UnknownAction(entry.name.clone(), other.to_string())),
+        };
+    }
+
+    match &entry.pattern {
+        Some(pattern) => build_regex_pattern(entry, pattern),
+        None => Err(RulePackError::MissingPayload {
+            name: entry.name.clone(),
+            field: "preset or pattern".to_string(),
+        }),
+    }
+}
+
+#[cfg(feature = "regex")]
+fn build_regex_pattern(entry: &RulePackEntry, pattern: &str) -> Result<Box<dyn Rule>, RulePackError> {
+    let rule = crate::rules::RegexRule::new(pattern, &entry.message, &entry.name)
+        .map_err(|e| RulePackError::InvalidPattern(entry.name.clone(), e.0))?;
+    Ok(Box::new(rule))
+}
+
+#[cfg(not(feature = "regex"))]
+fn build_regex_pattern(entry: &RulePackEntry, _pattern: &str) -> Result<Box<dyn Rule>, RulePackError> {
+    Err(RulePackError::RegexFeatureRequired(entry.name.clone()))
+}
+
+/// Build a `Sequence`-kind entry from its `tokens` field.
+fn build_sequence(entry: &RulePackEntry) -> Result<Box<dyn Rule>, RulePackError> {
+    if entry.tokens.is_empty() {
+        return Err(RulePackError::MissingPayload {
+            name: entry.name.clone(),
+            field: "tokens".to_string(),
+        });
+    }
+
+    let mut config = if entry.strict {
+        SequenceConfig::strict()
+    } else {
+        SequenceConfig::new()
+    };
+    if let Some(gaps) = entry.gaps {
+        config = config.allow_gaps(gaps);
+    }
+    config = config.stop_words(entry.stop_words.clone());
+
+    Ok(Box::new(ForbiddenSequenceRule::new(
+        entry.tokens.clone(),
+        &entry.message,
+        config,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_entry(kind: RulePackKind) -> RulePackEntry {
+        RulePackEntry {
+            name: "test".to_string(),
+            aliases: Vec::new(),
+            kind,
+            message: "blocked".to_string(),
+            preset: None,
+            pattern: None,
+            action: None,
+            placeholder: None,
+            tokens: Vec::new(),
+            strict: false,
+            gaps: None,
+            stop_words: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_from_json_parses_pack() {
+        let json = r#"{"rules":[{"name":"email_pii","kind":"pattern","message":"email found","preset":"email"}]}"#;
+        let pack = RulePack::from_json(json).unwrap();
+        assert_eq!(pack.rules.len(), 1);
+        assert_eq!(pack.rules[0].name, "email_pii");
+    }
+
+    #[test]
+    fn test_from_toml_parses_pack() {
+        let toml_src = "[[rules]]\nname = \"email_pii\"\nkind = \"pattern\"\nmessage = \"email found\"\npreset = \"email\"\n";
+        let pack = RulePack::from_toml(toml_src).unwrap();
+        assert_eq!(pack.rules.len(), 1);
+        assert_eq!(pack.rules[0].name, "email_pii");
+    }
+
+    #[test]
+    fn test_build_pattern_preset_entry() {
+        let mut entry = sample_entry(RulePackKind::Pattern);
+        entry.preset = Some("credit_card".to_string());
+        let pack = RulePack {
+            rules: alloc::vec![entry],
+        };
+
+        let mut built = build(&pack).unwrap();
+        assert_eq!(built.len(), 1);
+        assert_eq!(built[0].name, "test");
+        assert!(built[0].rule.feed("4111111111111111").is_block());
+    }
+
+    #[test]
+    fn test_build_pattern_rewrite_action_redacts() {
+        let mut entry = sample_entry(RulePackKind::Pattern);
+        entry.preset = Some("email".to_string());
+        entry.action = Some("rewrite".to_string());
+        entry.placeholder = Some("[REDACTED]".to_string());
+        let pack = RulePack {
+            rules: alloc::vec![entry],
+        };
+
+        let mut built = build(&pack).unwrap();
+        assert_eq!(
+            built[0].rule.feed("contact user@example.com").rewritten_text(),
+            Some("contact [REDACTED]")
+        );
+    }
+
+    #[test]
+    fn test_build_pattern_rewrite_action_missing_placeholder_errors() {
+        let mut entry = sample_entry(RulePackKind::Pattern);
+        entry.preset = Some("email".to_string());
+        entry.action = Some("rewrite".to_string());
+        let pack = RulePack {
+            rules: alloc::vec![entry],
+        };
+
+        assert_eq!(
+            build(&pack).err(),
+            Some(RulePackError::MissingPayload {
+                name: "test".to_string(),
+                field: "placeholder".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_build_pattern_unknown_action_errors() {
+        let mut entry = sample_entry(RulePackKind::Pattern);
+        entry.preset = Some("email".to_string());
+        entry.action = Some("redact_loudly".to_string());
+        let pack = RulePack {
+            rules: alloc::vec![entry],
+        };
+
+        assert_eq!(
+            build(&pack).err(),
+            Some(RulePackError::UnknownAction(
+                "test".to_string(),
+                "redact_loudly".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_build_pattern_unknown_preset_errors() {
+        let mut entry = sample_entry(RulePackKind::Pattern);
+        entry.preset = Some("phone_number".to_string());
+        let pack = RulePack {
+            rules: alloc::vec![entry],
+        };
+
+        assert_eq!(
+            build(&pack).err(),
+            Some(RulePackError::UnknownPreset("phone_number".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_build_pattern_missing_payload_errors() {
+        let entry = sample_entry(RulePackKind::Pattern);
+        let pack = RulePack {
+            rules: alloc::vec![entry],
+        };
+
+        assert_eq!(
+            build(&pack).err(),
+            Some(RulePackError::MissingPayload {
+                name: "test".to_string(),
+                field: "preset or pattern".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_build_sequence_entry_with_aliases() {
+        let mut entry = sample_entry(RulePackKind::Sequence);
+        entry.aliases = alloc::vec!["jailbreak".to_string()];
+        entry.tokens = alloc::vec!["how".to_string(), "to".to_string(), "hack".to_string()];
+        let pack = RulePack {
+            rules: alloc::vec![entry],
+        };
+
+        let mut built = build(&pack).unwrap();
+        assert_eq!(built[0].aliases, alloc::vec!["jailbreak".to_string()]);
+        assert!(built[0].rule.feed("how to safely hack").is_block());
+    }
+
+    #[test]
+    fn test_build_sequence_strict_rejects_gap() {
+        let mut entry = sample_entry(RulePackKind::Sequence);
+        entry.tokens = alloc::vec!["how".to_string(), "hack".to_string()];
+        entry.strict = true;
+        let pack = RulePack {
+            rules: alloc::vec![entry],
+        };
+
+        let mut built = build(&pack).unwrap();
+        assert!(built[0].rule.feed("how to hack").is_allow());
+    }
+
+    #[test]
+    fn test_build_sequence_missing_tokens_errors() {
+        let entry = sample_entry(RulePackKind::Sequence);
+        let pack = RulePack {
+            rules: alloc::vec![entry],
+        };
+
+        assert_eq!(
+            build(&pack).err(),
+            Some(RulePackError::MissingPayload {
+                name: "test".to_string(),
+                field: "tokens".to_string(),
+            })
+        );
+    }
+}
@@ -1,7 +1,11 @@
 //! Forbidden sequence detection using DFA-like state machines
 //!
 //! This rule detects forbidden token sequences in a streaming manner,
-//! handling partial matches across chunk boundaries.
+//! handling partial matches across chunk boundaries. Matching can
+//! optionally normalize case, fullwidth/halfwidth Unicode forms, and common
+//! leetspeak/homoglyph substitutions before comparing against tokens -- see
+//! [`SequenceConfig::case_insensitive`], [`SequenceConfig::unicode_normalize`],
+//! and [`SequenceConfig::fold_confusables`].
 
 use alloc::format;
 use alloc::string::{String, ToString};
@@ -9,6 +13,90 @@ use alloc::vec::Vec;
 
 use crate::core::{Decision, Rule};
 
+/// Fold a string to a canonical case for case-insensitive matching.
+///
+/// ASCII case folding is used by default; with the `std` feature, Unicode
+/// simple case folding (`to_lowercase`) is applied instead.
+fn fold_case(s: &str) -> String {
+    #[cfg(feature = "std")]
+    {
+        s.to_lowercase()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        s.to_ascii_lowercase()
+    }
+}
+
+/// Fold fullwidth/halfwidth Unicode forms to their ordinary ASCII
+/// equivalents, e.g. `ｈａｃｋ` -> `hack`, `　` (ideographic space) -> ` `.
+///
+/// This is a narrow, dependency-free subset of what full Unicode NFKC
+/// normalization would do (compatibility decomposition collapses these
+/// "fullwidth" compatibility characters into their canonical form); a
+/// complete NFKC implementation needs Unicode decomposition tables this
+/// crate doesn't vendor, so only the width-folding case is handled.
+fn fold_width(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            let cp = c as u32;
+            if (0xFF01..=0xFF5E).contains(&cp) {
+                char::from_u32(cp - 0xFEE0).unwrap_or(c)
+            } else if cp == 0x3000 {
+                ' '
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Fold a small set of common leetspeak digits and lookalike letters
+/// (homoglyphs) to the Latin letter they're meant to evade detection as,
+/// e.g. `h4ck` -> `hack`, `Аpple` (Cyrillic А) -> `apple`.
+///
+/// This is a static, hand-picked table, not a general implementation of
+/// Unicode confusable detection (UTS #39), which requires a much larger
+/// data table this crate doesn't vendor.
+fn fold_confusables(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '4' | '@' => 'a',
+            '3' => 'e',
+            '1' | '!' => 'i',
+            '0' => 'o',
+            '5' | '$' => 's',
+            '7' => 't',
+            'ı' => 'i',
+            'А' => 'a',
+            'Е' => 'e',
+            'О' => 'o',
+            'Р' => 'p',
+            'С' => 'c',
+            other => other,
+        })
+        .collect()
+}
+
+/// Apply the normalization steps enabled on `config`, in order: width
+/// folding, confusable folding, then case folding. Order matters -- width
+/// folding turns fullwidth digits/letters into plain ASCII so the
+/// confusable table (which only matches plain ASCII/Latin input) can see
+/// them, and case folding runs last so it sees already-canonicalized text.
+fn normalize_text(s: &str, config: &SequenceConfig) -> String {
+    let mut out = s.to_string();
+    if config.unicode_normalize {
+        out = fold_width(&out);
+    }
+    if config.fold_confusables {
+        out = fold_confusables(&out);
+    }
+    if config.case_insensitive {
+        out = fold_case(&out);
+    }
+    out
+}
+
 /// Configuration for sequence matching behavior
 #[derive(Debug, Clone)]
 pub struct SequenceConfig {
@@ -20,6 +108,23 @@ pub struct SequenceConfig {
     /// Words that break/reset the sequence when encountered
     /// Example: ["not", "never", "don't"] would reset on negations
     pub stop_words: Vec<String>,
+
+    /// Whether matching folds case so `SECRET` matches a lowercase token
+    /// - `false` (default): byte/case-sensitive matching (backward compatible)
+    /// - `true`: ASCII case folding, or Unicode simple folding with `std`
+    pub case_insensitive: bool,
+
+    /// Fold fullwidth/halfwidth Unicode forms to ASCII before matching,
+    /// e.g. `ｈａｃｋ` matches a `hack` token.
+    /// - `false` (default): no width folding
+    /// - `true`: fullwidth forms collapse to their ASCII equivalent
+    pub unicode_normalize: bool,
+
+    /// Fold common leetspeak digits and homoglyphs to the Latin letter
+    /// they're meant to evade detection as, e.g. `h4ck` matches `hack`.
+    /// - `false` (default): no confusable folding
+    /// - `true`: a small static table of substitutions is applied
+    pub fold_confusables: bool,
 }
 
 impl Default for SequenceConfig {
@@ -27,6 +132,9 @@ impl Default for SequenceConfig {
         Self {
             allow_gaps: true,
             stop_words: Vec::new(),
+            case_insensitive: false,
+            unicode_normalize: false,
+            fold_confusables: false,
         }
     }
 }
@@ -42,9 +150,30 @@ impl SequenceConfig {
         Self {
             allow_gaps: false,
             stop_words: Vec::new(),
+            case_insensitive: false,
+            unicode_normalize: false,
+            fold_confusables: false,
         }
     }
 
+    /// Fold case during matching so tokens match regardless of case.
+    pub fn case_insensitive(mut self, enabled: bool) -> Self {
+        self.case_insensitive = enabled;
+        self
+    }
+
+    /// Fold fullwidth/halfwidth Unicode forms to ASCII before matching.
+    pub fn unicode_normalize(mut self, enabled: bool) -> Self {
+        self.unicode_normalize = enabled;
+        self
+    }
+
+    /// Fold common leetspeak digits and homoglyphs before matching.
+    pub fn fold_confusables(mut self, enabled: bool) -> Self {
+        self.fold_confusables = enabled;
+        self
+    }
+
     /// Set whether gaps are allowed between tokens
     pub fn allow_gaps(mut self, allow: bool) -> Self {
         self.allow_gaps = allow;
@@ -117,6 +246,13 @@ pub struct ForbiddenSequenceRule {
     replacement: Option<String>,
     /// Last score from the most recent decision
     last_decision_score: u32,
+    /// Char offset, in the cumulative stream, of `buffer`'s first character
+    buffer_start: usize,
+    /// Char offset where the sequence's first token matched, set when the
+    /// state advances from 0 and cleared once the match completes or resets
+    match_start: Option<usize>,
+    /// `(start, end)` char span of the most recently completed match
+    last_match_span: Option<(usize, usize)>,
 }
 
 impl ForbiddenSequenceRule {
@@ -137,6 +273,9 @@ impl ForbiddenSequenceRule {
             score: 0,
             replacement: None,
             last_decision_score: 0,
+            buffer_start: 0,
+            match_start: None,
+            last_match_span: None,
         }
     }
 
@@ -161,6 +300,9 @@ impl ForbiddenSequenceRule {
             score,
             replacement: None,
             last_decision_score: 0,
+            buffer_start: 0,
+            match_start: None,
+            last_match_span: None,
         }
     }
 
@@ -175,16 +317,89 @@ impl ForbiddenSequenceRule {
             score: 0,
             replacement: Some(replacement.to_string()),
             last_decision_score: 0,
+            buffer_start: 0,
+            match_start: None,
+            last_match_span: None,
         }
     }
 
+    /// Set the score contributed when this rule matches.
+    pub fn set_score(&mut self, score: u32) {
+        self.score = score;
+    }
+
+    /// The literal token to search for if this rule is eligible for the
+    /// engine's shared single-token fast path (see
+    /// [`GuardEngine::evaluate_chunk`](crate::GuardEngine)), `None`
+    /// otherwise.
+    ///
+    /// A single-token, block-mode, unfolded, stop-word-free rule is nothing
+    /// more than a literal substring search, so many such rules added the
+    /// normal way (`add_rule`) can share one Aho-Corasick pass over the
+    /// chunk instead of each re-scanning it independently -- the same
+    /// automaton [`crate::rules::ForbiddenSetRule::strict`] already uses for
+    /// an explicit phrase list. Anything with more than one token (gaps
+    /// between tokens don't reduce to a single literal search), a rewrite
+    /// replacement (the automaton's per-chunk hit doesn't carry the matched
+    /// span needed to splice one in across a chunk boundary), or any
+    /// folding/stop-word configuration (the shared automaton has no per-rule
+    /// folding) keeps scanning on its own.
+    pub(crate) fn as_single_literal_block(&self) -> Option<&str> {
+        if self.tokens.len() == 1
+            && self.replacement.is_none()
+            && self.config.stop_words.is_empty()
+            && !self.config.case_insensitive
+            && !self.config.unicode_normalize
+            && !self.config.fold_confusables
+        {
+            Some(self.tokens[0].as_str())
+        } else {
+            None
+        }
+    }
+
+    /// Apply a match verdict the engine already determined from its shared
+    /// automaton (see [`Self::as_single_literal_block`]), instead of
+    /// re-scanning `chunk` with this rule's own buffer/state machine.
+    ///
+    /// Placing the matched span exactly (for [`Rule::last_match_span`])
+    /// would need this rule's own buffer/state to handle a match split
+    /// across a chunk boundary, which the fast path deliberately bypasses --
+    /// so `last_match_span` is cleared to `None` on a fast-pathed match
+    /// rather than risk reporting a stale or wrong offset.
+    pub(crate) fn feed_shared(&mut self, chunk: &str, matched: bool) -> Decision {
+        if chunk.is_empty() {
+            return Decision::Allow;
+        }
+        self.last_match_span = None;
+        if matched {
+            self.last_decision_score = self.score;
+            Decision::Block {
+                reason: self.reason.clone(),
+            }
+        } else {
+            self.last_decision_score = 0;
+            Decision::Allow
+        }
+    }
+
+    /// Normalize a token, stop word, or incoming chunk to the buffer's
+    /// comparison form (width folding, then confusable folding, then case
+    /// folding, per whichever are enabled on `self.config`).
+    fn normalize(&self, s: &str) -> String {
+        normalize_text(s, &self.config)
+    }
+
     /// Check if buffer contains any stop word and reset if found
     fn check_stop_words(&mut self) -> bool {
         for stop_word in &self.config.stop_words {
-            if self.buffer.contains(stop_word.as_str()) {
+            let needle = self.normalize(stop_word);
+            if self.buffer.contains(needle.as_str()) {
                 // Found a stop word - reset the sequence
                 self.state = 0;
+                self.buffer_start += self.buffer.chars().count();
                 self.buffer.clear();
+                self.match_start = None;
                 return true;
             }
         }
@@ -193,57 +408,95 @@ impl ForbiddenSequenceRule {
 
     /// Check if the current buffer + new text matches the next token
     fn check_match(&mut self, chunk: &str) -> bool {
-        // Append chunk to buffer
-        self.buffer.push_str(chunk);
+        // Append chunk to buffer, normalizing first when any folding is
+        // enabled so the buffer and token comparisons share one canonical
+        // form. Folding per-chunk keeps it O(n) and handles tokens split
+        // across feeds.
+        let normalizes = self.config.case_insensitive
+            || self.config.unicode_normalize
+            || self.config.fold_confusables;
+        if normalizes {
+            let folded = self.normalize(chunk);
+            self.buffer.push_str(&folded);
+        } else {
+            self.buffer.push_str(chunk);
+        }
 
         // Check for stop words first
         if self.check_stop_words() {
             return false;
         }
 
-        // For strict mode (no gaps), we need to ensure tokens are consecutive
-        if !self.config.allow_gaps && self.state > 0 {
-            // In strict mode, after finding a token, the next token must appear
-            // immediately (only whitespace allowed between)
-            let target = &self.tokens[self.state];
-            let trimmed = self.buffer.trim_start();
-            
-            if trimmed.starts_with(target) {
-                // Found next token immediately - advance
-                self.state += 1;
-                let after = target.len();
-                self.buffer = trimmed[after..].to_string();
-                
-                if self.state >= self.tokens.len() {
-                    return true;
-                }
-            } else if !trimmed.is_empty() && !trimmed.chars().all(char::is_whitespace) {
-                // Non-whitespace content that doesn't match - reset
-                self.state = 0;
-                self.buffer.clear();
-            }
-            return false;
-        }
-
         // Try to match tokens in sequence (with optional gaps)
         loop {
             if self.state >= self.tokens.len() {
                 return true; // Matched entire sequence
             }
 
-            let target = &self.tokens[self.state];
+            let target = self.normalize(&self.tokens[self.state]);
+            let target = target.as_str();
+
+            // In strict mode, each token after the first must appear
+            // immediately -- only whitespace allowed before it -- rather
+            // than anywhere later in the buffer; the first token may start
+            // anywhere and is found by the search below. This has to be
+            // re-checked on every iteration of this loop, not just once per
+            // `feed` call: an earlier draft checked it only once, outside
+            // the loop (based on the state at the start of the call), so a
+            // whole multi-token sequence arriving in one chunk fell through
+            // to the gap-allowing branch below for every token after the
+            // first, silently accepting gaps that strict mode rejects.
+            if !self.config.allow_gaps && self.state > 0 {
+                let trimmed = self.buffer.trim_start();
+
+                if trimmed.starts_with(target) {
+                    // Found next token immediately - advance. The match
+                    // starts right after however much leading whitespace
+                    // was trimmed.
+                    let leading_chars = self.buffer.chars().count() - trimmed.chars().count();
+                    let match_abs_start = self.buffer_start + leading_chars;
+                    if self.state == 0 {
+                        self.match_start = Some(match_abs_start);
+                    }
+                    self.state += 1;
+                    let after = target.len();
+                    self.buffer_start += leading_chars + target.chars().count();
+                    self.buffer = trimmed[after..].to_string();
+
+                    if self.state >= self.tokens.len() {
+                        let abs_end = match_abs_start + target.chars().count();
+                        self.last_match_span = self.match_start.take().map(|start| (start, abs_end));
+                        return true;
+                    }
+                    continue; // try matching the next token immediately
+                } else if !trimmed.is_empty() && !trimmed.chars().all(char::is_whitespace) {
+                    // Non-whitespace content that doesn't match - reset
+                    self.state = 0;
+                    self.buffer_start += self.buffer.chars().count();
+                    self.buffer.clear();
+                    self.match_start = None;
+                }
+                return false;
+            }
 
             // Check if buffer contains the target token
             if let Some(pos) = self.buffer.find(target) {
                 // Found the token - advance state
+                let match_abs_start = self.buffer_start + self.buffer[..pos].chars().count();
+                if self.state == 0 {
+                    self.match_start = Some(match_abs_start);
+                }
                 self.state += 1;
 
                 // Clear buffer up to and including the matched token
                 let after = pos + target.len();
+                self.buffer_start += self.buffer[..after].chars().count();
                 self.buffer = self.buffer[after..].to_string();
 
                 // Check if we've matched the entire sequence
                 if self.state >= self.tokens.len() {
+                    let abs_end = match_abs_start + target.chars().count();
+                    self.last_match_span = self.match_start.take().map(|start| (start, abs_end));
                     return true;
                 }
                 // Continue loop to try matching next token immediately
@@ -253,12 +506,22 @@ impl ForbiddenSequenceRule {
             }
         }
 
-        // Prevent buffer from growing unbounded
-        // Keep only the last N characters where N is the longest token length
-        let max_len = self.tokens.iter().map(|t| t.len()).max().unwrap_or(100);
-        if self.buffer.len() > max_len * 2 {
-            let keep = self.buffer.len() - max_len;
-            self.buffer = self.buffer[keep..].to_string();
+        // Prevent buffer from growing unbounded. Keep only the last N
+        // *characters* where N is the longest token length, cutting on a
+        // char boundary -- a byte-offset cut here would panic on multibyte
+        // input (emoji, accented letters, CJK) if it landed mid-character.
+        let max_chars = self.tokens.iter().map(|t| t.chars().count()).max().unwrap_or(100);
+        let buffer_chars = self.buffer.chars().count();
+        if buffer_chars > max_chars * 2 {
+            let skip = buffer_chars - max_chars;
+            let byte_offset = self
+                .buffer
+                .char_indices()
+                .nth(skip)
+                .map(|(i, _)| i)
+                .unwrap_or(self.buffer.len());
+            self.buffer_start += skip;
+            self.buffer = self.buffer[byte_offset..].to_string();
         }
 
         false
@@ -271,29 +534,42 @@ impl Rule for ForbiddenSequenceRule {
             return Decision::Allow;
         }
 
-        // Save original buffer content before check_match modifies it
+        // Save original buffer content (and its absolute start offset) before
+        // check_match modifies it
         let original_buffer = self.buffer.clone();
+        let original_buffer_start = self.buffer_start;
         let original_chunk = chunk.to_string();
-        
+
         if self.check_match(chunk) {
             // Match found - record score
             self.last_decision_score = self.score;
-            
+            let span = self.last_match_span;
+
             // Reset state after match to avoid repeated matches
             self.state = 0;
+            self.buffer_start += self.buffer.chars().count();
             self.buffer.clear();
-            
+
             // Check if this is a rewrite rule
             if let Some(ref replacement) = self.replacement {
                 // For rewrite, work with the complete text (original buffer + chunk)
                 let complete_text = format!("{}{}", original_buffer, original_chunk);
-                let mut rewritten = complete_text.clone();
-                
-                // Replace each matched token with the replacement
-                for token in &self.tokens {
-                    rewritten = rewritten.replace(token, replacement);
-                }
-                
+
+                // Replace only the matched span -- the char offsets recorded
+                // by check_match are relative to the cumulative stream, so
+                // rebase them onto complete_text before slicing. A plain
+                // `rewritten.replace(token, replacement)` would also clobber
+                // unrelated occurrences of a common token (e.g. "to", "is")
+                // elsewhere in the text.
+                let rewritten = match span {
+                    Some((start, end)) => {
+                        let local_start = start.saturating_sub(original_buffer_start);
+                        let local_end = end.saturating_sub(original_buffer_start);
+                        splice_char_range(&complete_text, local_start, local_end, replacement)
+                    }
+                    None => complete_text,
+                };
+
                 Decision::Rewrite {
                     replacement: rewritten,
                 }
@@ -314,6 +590,9 @@ impl Rule for ForbiddenSequenceRule {
         self.state = 0;
         self.buffer.clear();
         self.last_decision_score = 0;
+        self.buffer_start = 0;
+        self.match_start = None;
+        self.last_match_span = None;
     }
 
     fn name(&self) -> &str {
@@ -323,6 +602,36 @@ impl Rule for ForbiddenSequenceRule {
     fn last_score(&self) -> u32 {
         self.last_decision_score
     }
+
+    fn last_match_span(&self) -> Option<(usize, usize)> {
+        self.last_match_span
+    }
+}
+
+/// Replace the `[start, end)` char range of `text` with `replacement`,
+/// leaving everything outside that range untouched. Used by the rewrite path
+/// so only the matched sequence is substituted, not every occurrence of a
+/// token elsewhere in the text.
+fn splice_char_range(text: &str, start: usize, end: usize, replacement: &str) -> String {
+    let start = start.min(text.chars().count());
+    let end = end.min(text.chars().count()).max(start);
+
+    let start_byte = text
+        .char_indices()
+        .nth(start)
+        .map(|(i, _)| i)
+        .unwrap_or(text.len());
+    let end_byte = text
+        .char_indices()
+        .nth(end)
+        .map(|(i, _)| i)
+        .unwrap_or(text.len());
+
+    let mut out = String::with_capacity(text.len() + replacement.len());
+    out.push_str(&text[..start_byte]);
+    out.push_str(replacement);
+    out.push_str(&text[end_byte..]);
+    out
 }
 
 #[cfg(test)]
@@ -453,6 +762,44 @@ mod tests {
         assert!(rule.feed("how to not build a bomb").is_allow());
     }
 
+    #[test]
+    fn test_case_insensitive_matches_mixed_case() {
+        let config = SequenceConfig::new().case_insensitive(true);
+        let mut rule = ForbiddenSequenceRule::new(
+            vec!["secret", "password"],
+            "test",
+            config,
+        );
+
+        assert!(rule.feed("SECRET ").is_allow());
+        assert!(rule.feed("PASSWORD").is_block());
+    }
+
+    #[test]
+    fn test_case_insensitive_across_chunks() {
+        let config = SequenceConfig::new().case_insensitive(true);
+        let mut rule = ForbiddenSequenceRule::new(
+            vec!["how", "to", "hack"],
+            "test",
+            config,
+        );
+
+        assert!(rule.feed("HoW ").is_allow());
+        assert!(rule.feed("To Ha").is_allow());
+        assert!(rule.feed("CK").is_block());
+    }
+
+    #[test]
+    fn test_case_sensitive_default_unchanged() {
+        let mut rule = ForbiddenSequenceRule::with_gaps(
+            vec!["secret", "password"],
+            "test",
+        );
+
+        assert!(rule.feed("SECRET ").is_allow());
+        assert!(rule.feed("PASSWORD").is_allow()); // default stays case-sensitive
+    }
+
     #[test]
     fn test_multiple_stop_words() {
         let config = SequenceConfig::new().stop_words(vec!["not", "never", "don't"]);
@@ -465,4 +812,105 @@ mod tests {
         assert!(rule.feed("don't steal ").is_allow()); // Resets
         assert!(rule.feed("password").is_allow());
     }
+
+    #[test]
+    fn test_unicode_normalize_folds_fullwidth_forms() {
+        let config = SequenceConfig::new().unicode_normalize(true);
+        let mut rule = ForbiddenSequenceRule::new(vec!["hack"], "test", config);
+
+        assert!(rule.feed("\u{ff48}\u{ff41}\u{ff43}\u{ff4b}").is_block()); // "ｈａｃｋ"
+    }
+
+    #[test]
+    fn test_without_unicode_normalize_fullwidth_does_not_match() {
+        let mut rule = ForbiddenSequenceRule::with_gaps(vec!["hack"], "test");
+
+        assert!(rule.feed("\u{ff48}\u{ff41}\u{ff43}\u{ff4b}").is_allow());
+    }
+
+    #[test]
+    fn test_fold_confusables_matches_leetspeak() {
+        let config = SequenceConfig::new().fold_confusables(true);
+        let mut rule = ForbiddenSequenceRule::new(vec!["hack"], "test", config);
+
+        assert!(rule.feed("h4ck").is_block());
+    }
+
+    #[test]
+    fn test_fold_confusables_combined_with_case_insensitive() {
+        let config = SequenceConfig::new()
+            .fold_confusables(true)
+            .case_insensitive(true);
+        let mut rule = ForbiddenSequenceRule::new(vec!["hack"], "test", config);
+
+        assert!(rule.feed("H4CK").is_block());
+    }
+
+    #[test]
+    fn test_without_fold_confusables_leetspeak_does_not_match() {
+        let mut rule = ForbiddenSequenceRule::with_gaps(vec!["hack"], "test");
+
+        assert!(rule.feed("h4ck").is_allow());
+    }
+
+    #[test]
+    fn test_truncation_does_not_panic_on_multibyte_buffer() {
+        // Regression test: the buffer cap used to cut on a raw byte offset,
+        // which panics if it lands mid-character on multibyte UTF-8 input.
+        let mut rule = ForbiddenSequenceRule::with_gaps(vec!["hack"], "test");
+        let filler: String = core::iter::repeat('\u{1F600}').take(50).collect(); // emoji
+
+        assert!(rule.feed(&filler).is_allow());
+        assert!(rule.feed("hack").is_block());
+    }
+
+    #[test]
+    fn test_rewrite_preserves_original_text_outside_matched_tokens() {
+        let mut rule = ForbiddenSequenceRule::new_with_rewrite(vec!["café"], "[redacted]");
+
+        let decision = rule.feed("visit the café today");
+        assert_eq!(
+            decision.rewritten_text(),
+            Some("visit the [redacted] today")
+        );
+    }
+
+    #[test]
+    fn test_rewrite_does_not_clobber_unrelated_token_occurrences() {
+        // Regression test: rewriting used to `replace` every occurrence of
+        // each token across the whole buffer, so a common word like "to"
+        // elsewhere in the text got clobbered too.
+        let mut rule = ForbiddenSequenceRule::new_with_rewrite(vec!["how", "to", "hack"], "***");
+
+        let decision = rule.feed("how to hack is not how to fix");
+        assert_eq!(decision.rewritten_text(), Some("*** is not how to fix"));
+    }
+
+    #[test]
+    fn test_last_match_span_reports_offsets_for_single_chunk_match() {
+        let mut rule = ForbiddenSequenceRule::strict(vec!["secret", "leak"], "credential leak");
+
+        let decision = rule.feed("the secret leak happened");
+        assert!(decision.is_block());
+        assert_eq!(rule.last_match_span(), Some((4, 15)));
+        assert_eq!(&"the secret leak happened"[4..15], "secret leak");
+    }
+
+    #[test]
+    fn test_last_match_span_reports_offsets_across_chunks() {
+        let mut rule = ForbiddenSequenceRule::with_gaps(vec!["how", "hack"], "test");
+
+        assert!(rule.feed("tell me how to").is_allow());
+        let decision = rule.feed(" hack a system");
+        assert!(decision.is_block());
+        assert_eq!(rule.last_match_span(), Some((8, 19)));
+    }
+
+    #[test]
+    fn test_last_match_span_is_none_without_a_match() {
+        let mut rule = ForbiddenSequenceRule::strict(vec!["secret"], "test");
+        let decision = rule.feed("nothing interesting here");
+        assert!(decision.is_allow());
+        assert_eq!(rule.last_match_span(), None);
+    }
 }
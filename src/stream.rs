@@ -0,0 +1,174 @@
+//! Async stream/sink adapters that guard token streams directly from an
+//! event loop.
+//!
+//! [`GuardedStream`] wraps any `futures::Stream<Item = String>` (or a
+//! `tokio::sync::mpsc::Receiver<String>` via [`GuardedStream::from_receiver`])
+//! and feeds each item through an internal [`GuardEngine`] before yielding
+//! it: `Allow` passes the chunk through unchanged, `Rewrite` substitutes the
+//! replacement, and `Block` ends the stream after yielding one final
+//! `"[blocked: ...]"` item so the caller sees why it stopped. [`GuardedSink`]
+//! is the symmetric adapter for a writer: it guards each item before
+//! forwarding it, failing the send on `Block` instead of forwarding.
+//!
+//! Both implement the standard `futures` traits, so they drop directly into
+//! a `tokio::select!` loop that also handles timeouts and cancellation,
+//! instead of requiring callers to poll [`GuardEngine::feed`] by hand.
+//!
+//! This module is only compiled with the `async` feature enabled.
+
+use alloc::string::String;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures::{Sink, Stream};
+
+use crate::core::Decision;
+use crate::engine::GuardEngine;
+
+/// Wraps a `futures::Stream<Item = String>`, guarding each item it yields
+/// through an internal [`GuardEngine`].
+pub struct GuardedStream<S> {
+    inner: S,
+    engine: GuardEngine,
+    done: bool,
+}
+
+impl<S> GuardedStream<S> {
+    /// Wrap `inner`, guarding each item it yields through `engine`.
+    pub fn new(inner: S, engine: GuardEngine) -> Self {
+        Self {
+            inner,
+            engine,
+            done: false,
+        }
+    }
+
+    fn apply(&mut self, chunk: String) -> String {
+        match self.engine.feed(&chunk) {
+            Decision::Allow | Decision::Annotate { .. } => chunk,
+            Decision::Rewrite { replacement } => replacement,
+            Decision::Block { reason } => {
+                self.done = true;
+                alloc::format!("[blocked: {}]", reason)
+            }
+        }
+    }
+}
+
+impl<S> Stream for GuardedStream<S>
+where
+    S: Stream<Item = String> + Unpin,
+{
+    type Item = String;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+        let this = self.get_mut();
+        match Pin::new(&mut this.inner).poll_next(cx) {
+            Poll::Ready(Some(chunk)) => Poll::Ready(Some(this.apply(chunk))),
+            Poll::Ready(None) => {
+                this.done = true;
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Minimal adapter from a `tokio::sync::mpsc::Receiver` to `futures::Stream`,
+/// used by [`GuardedStream::from_receiver`] so callers don't need the
+/// separate `tokio-stream` crate just to guard a channel.
+struct ReceiverStream {
+    rx: tokio::sync::mpsc::Receiver<String>,
+}
+
+impl Stream for ReceiverStream {
+    type Item = String;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl GuardedStream<ReceiverStream> {
+    /// Wrap a `tokio::sync::mpsc::Receiver<String>`, guarding each item it
+    /// yields through `engine`.
+    pub fn from_receiver(rx: tokio::sync::mpsc::Receiver<String>, engine: GuardEngine) -> Self {
+        Self::new(ReceiverStream { rx }, engine)
+    }
+}
+
+/// Error produced by [`GuardedSink`]: either the guard blocked an item, or
+/// the wrapped writer's own sink failed.
+#[derive(Debug)]
+pub enum GuardedSinkError<E> {
+    /// The guard engine blocked this item.
+    Blocked {
+        /// The block reason reported by the engine.
+        reason: String,
+    },
+    /// The wrapped writer's sink returned an error.
+    Inner(E),
+}
+
+/// A sink adapter symmetric to [`GuardedStream`]: guards each item through an
+/// internal [`GuardEngine`] before forwarding it to the wrapped writer.
+///
+/// `Allow` and `Annotate` items are forwarded unchanged, `Rewrite` forwards
+/// the replacement, and `Block` fails the send with
+/// [`GuardedSinkError::Blocked`] instead of forwarding, so a blocked item is
+/// observed as a normal sink error rather than silently dropped.
+pub struct GuardedSink<W> {
+    inner: W,
+    engine: GuardEngine,
+}
+
+impl<W> GuardedSink<W> {
+    /// Wrap `inner`, guarding each item sent to it through `engine`.
+    pub fn new(inner: W, engine: GuardEngine) -> Self {
+        Self { inner, engine }
+    }
+}
+
+impl<W> Sink<String> for GuardedSink<W>
+where
+    W: Sink<String> + Unpin,
+{
+    type Error = GuardedSinkError<W::Error>;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        Pin::new(&mut this.inner)
+            .poll_ready(cx)
+            .map_err(GuardedSinkError::Inner)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: String) -> Result<(), Self::Error> {
+        let this = self.get_mut();
+        match this.engine.feed(&item) {
+            Decision::Allow | Decision::Annotate { .. } => Pin::new(&mut this.inner)
+                .start_send(item)
+                .map_err(GuardedSinkError::Inner),
+            Decision::Rewrite { replacement } => Pin::new(&mut this.inner)
+                .start_send(replacement)
+                .map_err(GuardedSinkError::Inner),
+            Decision::Block { reason } => Err(GuardedSinkError::Blocked { reason }),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        Pin::new(&mut this.inner)
+            .poll_flush(cx)
+            .map_err(GuardedSinkError::Inner)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        Pin::new(&mut this.inner)
+            .poll_close(cx)
+            .map_err(GuardedSinkError::Inner)
+    }
+}
@@ -2,16 +2,67 @@
 
 use wasm_bindgen::prelude::*;
 use alloc::boxed::Box;
+use alloc::format;
+use alloc::rc::Rc;
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::cell::RefCell;
 
-use crate::{GuardEngine, Decision};
-use crate::rules::{ForbiddenSequenceRule, PatternRule};
+use crate::{GuardEngine, Decision, Rule};
+use crate::rules::{BayesRule, ForbiddenSequenceRule, PatternRule};
+
+/// Wraps a JS callback so it can sit behind `GuardEngine`'s `Send + Sync`
+/// observer slot.
+///
+/// Sound only because `wasm32-unknown-unknown` has no real threads: nothing
+/// can call this from anywhere but the single JS/Rust thread that owns it.
+struct JsCallback(js_sys::Function);
+
+unsafe impl Send for JsCallback {}
+unsafe impl Sync for JsCallback {}
+
+/// Shares a [`BayesRule`] between the engine's rule list and the wasm
+/// `trainSpam`/`trainHam` calls, which need to keep training it after it has
+/// been handed to the engine.
+///
+/// Sound only because `wasm32-unknown-unknown` has no real threads, same as
+/// [`JsCallback`] above.
+struct SharedBayesRule(Rc<RefCell<BayesRule>>);
+
+unsafe impl Send for SharedBayesRule {}
+unsafe impl Sync for SharedBayesRule {}
+
+impl Rule for SharedBayesRule {
+    fn feed(&mut self, chunk: &str) -> Decision {
+        self.0.borrow_mut().feed(chunk)
+    }
+
+    fn reset(&mut self) {
+        self.0.borrow_mut().reset();
+    }
+
+    fn name(&self) -> &str {
+        "bayes"
+    }
+
+    fn last_score(&self) -> u32 {
+        self.0.borrow().last_score()
+    }
+}
 
 /// WASM-compatible wrapper for GuardEngine
 #[wasm_bindgen]
 pub struct WasmGuardEngine {
     engine: GuardEngine,
+    /// Lazily added to `engine`'s rules on the first `trainSpam`/`trainHam`
+    /// call, so an engine that never trains one doesn't pay its neutral
+    /// per-chunk score.
+    bayes: Rc<RefCell<BayesRule>>,
+    bayes_added: bool,
+    /// When set, `feed` returns the richer `feed_report`-backed shape
+    /// instead of the default `{type, reason}` object. Off by default so
+    /// existing callers keep the simple shape.
+    rich_reports: bool,
 }
 
 #[wasm_bindgen]
@@ -21,6 +72,9 @@ impl WasmGuardEngine {
     pub fn new() -> Self {
         Self {
             engine: GuardEngine::new(),
+            bayes: Rc::new(RefCell::new(BayesRule::new())),
+            bayes_added: false,
+            rich_reports: false,
         }
     }
 
@@ -29,6 +83,44 @@ impl WasmGuardEngine {
     pub fn with_score_threshold(threshold: u32) -> Self {
         Self {
             engine: GuardEngine::with_score_threshold(threshold),
+            bayes: Rc::new(RefCell::new(BayesRule::new())),
+            bayes_added: false,
+            rich_reports: false,
+        }
+    }
+
+    /// Toggle whether `feed` returns the richer match-report shape (rule id,
+    /// matched text, byte offset, score, and running total for every rule
+    /// that fired) instead of the default `{type, reason}` object.
+    #[wasm_bindgen(js_name = setRichReports)]
+    pub fn set_rich_reports(&mut self, enabled: bool) {
+        self.rich_reports = enabled;
+    }
+
+    /// Train the Bayesian scoring rule on a message known to be spam.
+    ///
+    /// The rule is added to the engine's scoring pipeline on first use.
+    #[wasm_bindgen(js_name = trainSpam)]
+    pub fn train_spam(&mut self, text: &str) {
+        self.bayes.borrow_mut().train_spam(text);
+        self.ensure_bayes_rule_added();
+    }
+
+    /// Train the Bayesian scoring rule on a message known to be ham
+    /// (legitimate).
+    ///
+    /// The rule is added to the engine's scoring pipeline on first use.
+    #[wasm_bindgen(js_name = trainHam)]
+    pub fn train_ham(&mut self, text: &str) {
+        self.bayes.borrow_mut().train_ham(text);
+        self.ensure_bayes_rule_added();
+    }
+
+    /// Add the shared Bayes rule to the engine exactly once.
+    fn ensure_bayes_rule_added(&mut self) {
+        if !self.bayes_added {
+            self.engine.add_rule(Box::new(SharedBayesRule(Rc::clone(&self.bayes))));
+            self.bayes_added = true;
         }
     }
 
@@ -56,6 +148,20 @@ impl WasmGuardEngine {
         self.engine.add_rule(Box::new(rule));
     }
 
+    /// Add a forbidden sequence rule that tags matches with `marker` instead
+    /// of blocking the stream (see `Decision::Annotate`).
+    #[wasm_bindgen(js_name = addAnnotatedForbiddenSequence)]
+    pub fn add_annotated_forbidden_sequence(
+        &mut self,
+        tokens: Vec<JsValue>,
+        marker: &str,
+        reason: &str,
+    ) {
+        let token_strings: Vec<String> = tokens.iter().filter_map(|v| v.as_string()).collect();
+        let rule = ForbiddenSequenceRule::with_gaps(token_strings, reason);
+        self.engine.add_annotate_rule(marker, Box::new(rule));
+    }
+
     /// Add an email detection rule
     #[wasm_bindgen(js_name = addEmailBlocker)]
     pub fn add_email_blocker(&mut self, reason: &str) {
@@ -119,12 +225,40 @@ impl WasmGuardEngine {
         self.engine.add_rule(Box::new(rule));
     }
 
-    /// Process a chunk of text
-    /// Returns a JsValue with: { type: "allow" | "block" | "rewrite", reason?: string, replacement?: string }
+    /// Compile and add a boolean expression rule (see `streamguard::rules::expr`),
+    /// e.g. `"email AND NOT url"`. Returns an error message on a syntax error.
+    #[wasm_bindgen(js_name = addExpression)]
+    pub fn add_expression(&mut self, source: &str) -> Result<(), JsValue> {
+        self.engine
+            .add_expression(source)
+            .map_err(|err| JsValue::from_str(&format!("{:?}", err)))
+    }
+
+    /// Load an Adblock-Plus-style filter list (see
+    /// `streamguard::rules::filterlist`). Plain lines block; `@@`-prefixed
+    /// lines allowlist and override a would-be block.
+    #[wasm_bindgen(js_name = loadFilterList)]
+    pub fn load_filter_list(&mut self, source: &str) {
+        self.engine.load_filter_list(source);
+    }
+
+    /// Process a chunk of text.
+    ///
+    /// By default returns a JsValue shaped `{ type: "allow" | "block" |
+    /// "rewrite" | "annotate", reason?: string, replacement?: string,
+    /// marker?: string, score?: number }` (`marker` and `score` are set for
+    /// `annotate` only). When `setRichReports(true)` has been called,
+    /// returns the `feed_report`-backed shape instead: `{ type, reason?,
+    /// replacement?, marker?, score, matches: [{ rule, kind, detail,
+    /// matchedText, offset, score }] }`.
     #[wasm_bindgen]
     pub fn feed(&mut self, chunk: &str) -> JsValue {
+        if self.rich_reports {
+            return self.feed_rich(chunk);
+        }
+
         let decision = self.engine.feed(chunk);
-        
+
         match decision {
             Decision::Allow => {
                 let obj = js_sys::Object::new();
@@ -143,15 +277,102 @@ impl WasmGuardEngine {
                 js_sys::Reflect::set(&obj, &"replacement".into(), &replacement.into()).unwrap();
                 obj.into()
             }
+            Decision::Annotate {
+                marker,
+                reason,
+                score,
+            } => {
+                let obj = js_sys::Object::new();
+                js_sys::Reflect::set(&obj, &"type".into(), &"annotate".into()).unwrap();
+                js_sys::Reflect::set(&obj, &"marker".into(), &marker.into()).unwrap();
+                js_sys::Reflect::set(&obj, &"reason".into(), &reason.into()).unwrap();
+                js_sys::Reflect::set(&obj, &"score".into(), &(score as f64).into()).unwrap();
+                obj.into()
+            }
         }
     }
 
+    /// Build the richer `feed_report`-backed object returned by `feed` once
+    /// `setRichReports(true)` has been called.
+    fn feed_rich(&mut self, chunk: &str) -> JsValue {
+        let report = self.engine.feed_report(chunk);
+
+        let obj = js_sys::Object::new();
+        match &report.decision {
+            Decision::Allow => {
+                js_sys::Reflect::set(&obj, &"type".into(), &"allow".into()).unwrap();
+            }
+            Decision::Block { reason } => {
+                js_sys::Reflect::set(&obj, &"type".into(), &"block".into()).unwrap();
+                js_sys::Reflect::set(&obj, &"reason".into(), &reason.as_str().into()).unwrap();
+            }
+            Decision::Rewrite { replacement } => {
+                js_sys::Reflect::set(&obj, &"type".into(), &"rewrite".into()).unwrap();
+                js_sys::Reflect::set(&obj, &"replacement".into(), &replacement.as_str().into()).unwrap();
+            }
+            Decision::Annotate { marker, reason, .. } => {
+                js_sys::Reflect::set(&obj, &"type".into(), &"annotate".into()).unwrap();
+                js_sys::Reflect::set(&obj, &"marker".into(), &marker.as_str().into()).unwrap();
+                js_sys::Reflect::set(&obj, &"reason".into(), &reason.as_str().into()).unwrap();
+            }
+        }
+        js_sys::Reflect::set(&obj, &"score".into(), &(report.score as f64).into()).unwrap();
+
+        let matches = js_sys::Array::new();
+        for m in &report.matches {
+            let match_obj = js_sys::Object::new();
+            js_sys::Reflect::set(&match_obj, &"rule".into(), &m.rule.as_str().into()).unwrap();
+            js_sys::Reflect::set(
+                &match_obj,
+                &"kind".into(),
+                &match m.kind {
+                    crate::MatchKind::Block => "block",
+                    crate::MatchKind::Rewrite => "rewrite",
+                    crate::MatchKind::Annotate => "annotate",
+                }
+                .into(),
+            )
+            .unwrap();
+            js_sys::Reflect::set(&match_obj, &"detail".into(), &m.detail.as_str().into()).unwrap();
+            js_sys::Reflect::set(&match_obj, &"matchedText".into(), &m.matched_text.as_str().into()).unwrap();
+            js_sys::Reflect::set(&match_obj, &"offset".into(), &(m.offset as f64).into()).unwrap();
+            js_sys::Reflect::set(&match_obj, &"score".into(), &(m.score as f64).into()).unwrap();
+            matches.push(&match_obj);
+        }
+        js_sys::Reflect::set(&obj, &"matches".into(), &matches).unwrap();
+
+        obj.into()
+    }
+
     /// Reset the engine
     #[wasm_bindgen]
     pub fn reset(&mut self) {
         self.engine.reset();
     }
 
+    /// Register a JS callback invoked as `callback(reason, score)` whenever
+    /// `feed` blocks the stream. A second call replaces the previous callback.
+    #[wasm_bindgen(js_name = setOnBlock)]
+    pub fn set_on_block(&mut self, callback: js_sys::Function) {
+        let callback = JsCallback(callback);
+        self.engine.set_on_block(Box::new(move |reason, score| {
+            let this = JsValue::NULL;
+            let _ = callback.0.call2(&this, &reason.into(), &(score as f64).into());
+        }));
+    }
+
+    /// Register a JS callback invoked as `callback(replacement, score)`
+    /// whenever `feed` rewrites the stream. A second call replaces the
+    /// previous callback.
+    #[wasm_bindgen(js_name = setOnRewrite)]
+    pub fn set_on_rewrite(&mut self, callback: js_sys::Function) {
+        let callback = JsCallback(callback);
+        self.engine.set_on_rewrite(Box::new(move |replacement, score| {
+            let this = JsValue::NULL;
+            let _ = callback.0.call2(&this, &replacement.into(), &(score as f64).into());
+        }));
+    }
+
     /// Check if the engine has been stopped
     #[wasm_bindgen(js_name = isStopped)]
     pub fn is_stopped(&self) -> bool {
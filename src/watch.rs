@@ -0,0 +1,64 @@
+//! Hot-reloading a [`GuardEngine`]'s rule set from a config file on disk
+//!
+//! [`ConfigWatcher`] re-parses a [`crate::rules::rulepack::RulePack`] file
+//! when it changes and calls [`GuardEngine::reload_rules`] so a deployed
+//! guard can pick up new forbidden sequences or patterns without tearing
+//! down in-progress streams -- the same "reload without dropping
+//! connections" shape as a mail server's config hot-reload.
+//!
+//! This is a polling helper, not a background thread: `feed` takes
+//! `&mut self`, so there is no safe point for a watcher to swap rules in
+//! except one the caller chooses. Drive [`ConfigWatcher::poll`] from
+//! whatever timer or event loop already owns the engine (a tick before each
+//! batch of `feed` calls, a periodic task, etc.) -- this module adds no
+//! threading or async runtime dependency of its own, unlike
+//! [`crate::worker`], which genuinely needs one to run rules concurrently.
+//!
+//! This module requires the `watch` feature, which implies `config` and
+//! `std` (file metadata and reads aren't available otherwise).
+
+use alloc::string::{String, ToString};
+
+use crate::engine::GuardEngine;
+use crate::rules::rulepack::RulePackError;
+
+/// Polls a rule-pack file's mtime and reloads a [`GuardEngine`] when it changes.
+pub struct ConfigWatcher {
+    path: String,
+    last_modified: Option<std::time::SystemTime>,
+}
+
+impl ConfigWatcher {
+    /// Watch `path`. The first [`Self::poll`] always reloads, establishing
+    /// the baseline mtime.
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            last_modified: None,
+        }
+    }
+
+    /// Check the watched file's mtime and, if it changed since the last
+    /// poll, re-parse it and reload `engine`'s rules via
+    /// [`GuardEngine::reload_rules`].
+    ///
+    /// Returns `Ok(true)` if a reload happened, `Ok(false)` if the file is
+    /// unchanged, or `Err` if it changed but failed to read, parse, or
+    /// build -- in which case `engine` keeps its previous rules untouched.
+    pub fn poll(&mut self, engine: &mut GuardEngine) -> Result<bool, RulePackError> {
+        let metadata =
+            std::fs::metadata(&self.path).map_err(|e| RulePackError::Io(e.to_string()))?;
+        let modified = metadata
+            .modified()
+            .map_err(|e| RulePackError::Io(e.to_string()))?;
+
+        if self.last_modified == Some(modified) {
+            return Ok(false);
+        }
+
+        let fresh = GuardEngine::from_config_file(&self.path)?;
+        engine.reload_rules(fresh.into_rules());
+        self.last_modified = Some(modified);
+        Ok(true)
+    }
+}
@@ -0,0 +1,198 @@
+//! Background worker pool for asynchronous rule evaluation
+//!
+//! Synchronous rules run directly on the streaming hot path, but some rules
+//! need to call out to an external moderation service, a hosted LLM
+//! classifier, or an embedding lookup. Those are I/O-bound and would stall
+//! `feed` if run inline. This module provides a small, bounded worker pool
+//! that drives [`AsyncRule`](crate::core::AsyncRule) evaluations off the hot
+//! path so expensive rules can participate without blocking the stream.
+//!
+//! The pool is configured the same way as the external-writer config used
+//! elsewhere in the ecosystem: a `backlog` (a queue size that the pool
+//! records and reports; dispatch does not queue or limit by it) and a
+//! `capacity` (how many worker tasks run concurrently). A per-rule `timeout_ms` bounds each evaluation;
+//! a rule that does not answer in time yields a configurable decision so a
+//! slow classifier cannot hang the stream.
+//!
+//! This module is only compiled with the `async` feature enabled.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use crate::core::{AsyncRule, Decision};
+
+/// What a rule's decision defaults to when its evaluation exceeds `timeout_ms`.
+///
+/// The two policies mirror the usual fail-open / fail-closed trade-off for
+/// security middleware: prefer availability, or prefer safety.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeoutPolicy {
+    /// Fail open: a timed-out rule is treated as `Allow`.
+    FailOpen,
+    /// Fail closed: a timed-out rule blocks with the given reason.
+    FailClosed(alloc::string::String),
+}
+
+impl Default for TimeoutPolicy {
+    fn default() -> Self {
+        TimeoutPolicy::FailOpen
+    }
+}
+
+/// Configuration for the background worker pool.
+///
+/// `backlog` and `capacity` follow the same naming as the external-writer
+/// configuration so operators have one mental model across components.
+#[derive(Debug, Clone)]
+pub struct WorkerConfig {
+    /// Queue size reported by [`WorkerPool::backlog`]; dispatch does not
+    /// queue or back-pressure by it.
+    pub backlog: usize,
+    /// Number of worker tasks evaluating rules concurrently.
+    pub capacity: usize,
+    /// Per-rule evaluation timeout in milliseconds.
+    pub timeout_ms: u64,
+    /// Decision applied to a rule whose evaluation times out.
+    pub on_timeout: TimeoutPolicy,
+}
+
+impl Default for WorkerConfig {
+    fn default() -> Self {
+        Self {
+            backlog: 64,
+            capacity: 8,
+            timeout_ms: 250,
+            on_timeout: TimeoutPolicy::FailOpen,
+        }
+    }
+}
+
+impl WorkerConfig {
+    /// Create a configuration with the given backlog and capacity.
+    pub fn new(backlog: usize, capacity: usize) -> Self {
+        Self {
+            backlog: backlog.max(1),
+            capacity: capacity.max(1),
+            ..Self::default()
+        }
+    }
+
+    /// Set the per-rule timeout in milliseconds.
+    pub fn timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Fail closed with `reason` when a rule times out instead of allowing.
+    pub fn fail_closed(mut self, reason: &str) -> Self {
+        self.on_timeout = TimeoutPolicy::FailClosed(reason.into());
+        self
+    }
+}
+
+/// A bounded pool that evaluates async rules concurrently off the hot path.
+///
+/// Each call to [`WorkerPool::dispatch`] fans the chunk out to every rule,
+/// bounds concurrency by `capacity` via a semaphore, applies the per-rule
+/// timeout, and collects the resulting decisions in rule order so the engine
+/// can merge them with the usual first-block/scoring/rewrite-chain semantics.
+pub struct WorkerPool {
+    config: WorkerConfig,
+    semaphore: alloc::sync::Arc<tokio::sync::Semaphore>,
+}
+
+impl WorkerPool {
+    /// Build a pool from the given configuration.
+    pub fn new(config: WorkerConfig) -> Self {
+        let permits = config.capacity;
+        Self {
+            config,
+            semaphore: alloc::sync::Arc::new(tokio::sync::Semaphore::new(permits)),
+        }
+    }
+
+    /// The configured backlog, as given in [`WorkerConfig`]; dispatch does
+    /// not consult it.
+    pub fn backlog(&self) -> usize {
+        self.config.backlog
+    }
+
+    /// Evaluate `chunk` against every rule concurrently and return the
+    /// per-rule decisions in rule order.
+    ///
+    /// A rule that exceeds `timeout_ms` resolves to the configured
+    /// [`TimeoutPolicy`] rather than stalling the batch.
+    pub async fn dispatch(
+        &self,
+        rules: &mut [Box<dyn AsyncRule>],
+        chunk: &str,
+    ) -> Vec<Decision> {
+        let timeout = Duration::from_millis(self.config.timeout_ms);
+        let mut tasks = Vec::with_capacity(rules.len());
+
+        for rule in rules.iter_mut() {
+            let semaphore = self.semaphore.clone();
+            let policy = self.config.on_timeout.clone();
+            tasks.push(async move {
+                // Acquire the permit inside the task itself, not before it's
+                // pushed -- otherwise the first `capacity` iterations of this
+                // loop drain the semaphore into un-polled futures and the
+                // next `acquire_owned().await` blocks forever, since nothing
+                // can release a permit until `join_all` below starts polling.
+                let _permit = semaphore
+                    .acquire_owned()
+                    .await
+                    .expect("worker semaphore closed");
+                match tokio::time::timeout(timeout, rule.feed(chunk)).await {
+                    Ok(decision) => decision,
+                    Err(_) => match policy {
+                        TimeoutPolicy::FailOpen => Decision::Allow,
+                        TimeoutPolicy::FailClosed(reason) => Decision::Block { reason },
+                    },
+                }
+            });
+        }
+
+        futures::future::join_all(tasks).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct AllowRule;
+
+    #[async_trait::async_trait]
+    impl AsyncRule for AllowRule {
+        async fn feed(&mut self, _chunk: &str) -> Decision {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+            Decision::Allow
+        }
+
+        fn reset(&mut self) {}
+
+        fn name(&self) -> &str {
+            "allow_rule"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_more_rules_than_capacity_does_not_deadlock() {
+        let pool = WorkerPool::new(WorkerConfig::new(64, 8));
+        let mut rules: Vec<Box<dyn AsyncRule>> = (0..10)
+            .map(|_| Box::new(AllowRule) as Box<dyn AsyncRule>)
+            .collect();
+
+        let decisions = tokio::time::timeout(
+            Duration::from_secs(5),
+            pool.dispatch(&mut rules, "chunk"),
+        )
+        .await
+        .expect("dispatch must not hang when rule count exceeds capacity");
+
+        assert_eq!(decisions.len(), 10);
+        assert!(decisions.iter().all(|d| d.is_allow()));
+    }
+}
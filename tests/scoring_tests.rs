@@ -81,6 +81,48 @@ fn test_multiple_rules_cumulative_score_exceeds_threshold() {
     assert_eq!(engine.current_score(), 110);
 }
 
+#[test]
+fn test_feed_scored_reports_running_total_before_threshold() {
+    let mut engine = GuardEngine::with_score_threshold(100);
+
+    engine.add_rule(Box::new(ForbiddenSequenceRule::new_with_score(
+        vec!["hack".to_string()],
+        "hacking",
+        60,
+    )));
+
+    let scored = engine.feed_scored("how to hack");
+    assert!(scored.decision.is_allow());
+    assert_eq!(scored.score, 60);
+    assert_eq!(scored.total_score, 60);
+    assert_eq!(scored.score_details, vec![("forbidden_sequence".to_string(), 60)]);
+}
+
+#[test]
+fn test_feed_scored_blocks_once_cumulative_total_crosses_threshold() {
+    let mut engine = GuardEngine::with_score_threshold(100);
+
+    engine.add_rule(Box::new(ForbiddenSequenceRule::new_with_score(
+        vec!["hack".to_string()],
+        "hacking",
+        60,
+    )));
+    engine.add_rule(Box::new(ForbiddenSequenceRule::new_with_score(
+        vec!["exploit".to_string()],
+        "exploit",
+        50,
+    )));
+
+    let first = engine.feed_scored("how to hack");
+    assert!(first.decision.is_allow());
+    assert_eq!(first.total_score, 60);
+
+    let second = engine.feed_scored("and exploit too");
+    assert!(second.decision.is_block());
+    assert_eq!(second.score, 50);
+    assert_eq!(second.total_score, 110);
+}
+
 #[test]
 fn test_score_details_per_rule() {
     let mut engine = GuardEngine::new();
@@ -175,3 +217,61 @@ fn test_weighted_scoring() {
     let _decision = engine.feed("warning: delete or kill the process");
     assert_eq!(engine.current_score(), 160);
 }
+
+/// Deterministic clock for exercising wall-clock score decay.
+struct FakeClock {
+    secs: std::sync::Mutex<f64>,
+}
+
+impl FakeClock {
+    fn new() -> Self {
+        Self {
+            secs: std::sync::Mutex::new(0.0),
+        }
+    }
+
+    fn advance(&self, delta: f64) {
+        *self.secs.lock().unwrap() += delta;
+    }
+}
+
+impl streamguard::Clock for FakeClock {
+    fn now_secs(&self) -> f64 {
+        *self.secs.lock().unwrap()
+    }
+}
+
+#[test]
+fn test_time_decay_dissipates_sparse_hits() {
+    use std::sync::Arc;
+
+    let clock = Arc::new(FakeClock::new());
+
+    // 10 points/sec of decay; threshold well above a single hit.
+    let mut engine = GuardEngine::with_time_decay(10.0);
+    engine.set_clock(Box::new(ArcClock(clock.clone())));
+
+    engine.add_rule(Box::new(ForbiddenSequenceRule::new_with_score(
+        vec!["hack".to_string()],
+        "suspicious",
+        50,
+    )));
+
+    // First hit accumulates 50.
+    engine.feed("hack");
+    assert_eq!(engine.current_score(), 50);
+
+    // After 10 seconds of silence, 100 points would decay away -> clamped to 0.
+    clock.advance(10.0);
+    engine.feed("nothing here");
+    assert_eq!(engine.current_score(), 0);
+}
+
+/// Shareable wrapper so the test can both hold and advance the clock.
+struct ArcClock(std::sync::Arc<FakeClock>);
+
+impl streamguard::Clock for ArcClock {
+    fn now_secs(&self) -> f64 {
+        self.0.now_secs()
+    }
+}